@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+use sudoku_solver::Sudoku;
+
+const PUZZLES: [(&str, &str); 4] = [
+    (
+        "gelderlander-20220308",
+        include_str!("../examples/gelderlander-20220308.txt"),
+    ),
+    (
+        "heuristic_2_1",
+        include_str!("../examples/heuristic_2_1.txt"),
+    ),
+    (
+        "daily_telegraph",
+        include_str!("../examples/daily_telegraph.txt"),
+    ),
+    ("hard", include_str!("../examples/hard.txt")),
+];
+
+const MEASURE_TIME: Duration = Duration::from_millis(500);
+
+/// Runs the action repeatedly for a while and reports the number of runs per second
+fn bench(name: &str, action: impl Fn()) {
+    let start = Instant::now();
+    let mut runs = 0_u32;
+
+    while start.elapsed() < MEASURE_TIME {
+        action();
+        runs += 1;
+    }
+
+    let per_run = start.elapsed() / runs;
+    println!(
+        "{:40} {:>10.1} runs/s {:>12?}/run",
+        name,
+        1.0 / per_run.as_secs_f64(),
+        per_run
+    );
+}
+
+fn main() {
+    for (name, puzzle) in PUZZLES {
+        bench(&format!("parse {}", name), || {
+            Sudoku::parse(puzzle).unwrap();
+        });
+
+        let sudoku = Sudoku::parse(puzzle).unwrap();
+
+        bench(&format!("solve {}", name), || {
+            sudoku.clone().solve().unwrap();
+        });
+        bench(&format!("solve_complete {}", name), || {
+            sudoku.clone().solve_complete().unwrap();
+        });
+        bench(&format!("count_solutions {}", name), || {
+            sudoku.count_solutions(2);
+        });
+    }
+}
@@ -4,15 +4,99 @@ pub const GRID_ROWS: usize = GRID_COLUMNS;
 pub const GRID_SIZE: usize = GRID_ROWS * GRID_COLUMNS;
 pub const GRID_INDEX_MAX: usize = (GRID_ROWS - 1) * GRID_COLUMNS + 1;
 
-pub type CellOptions = [bool; GRID_COLUMNS + 1];
-pub type CellOrigins = [Option<usize>; GRID_COLUMNS + 1];
+/// Per value, bit n is set when cell n has the value as option
+pub type CellOrigins = [u128; GRID_COLUMNS + 1];
 pub type SudokuResult<RT = ()> = Result<RT, SudokuError>;
 use crate::Cell;
 pub type Cells = [Cell; GRID_SIZE];
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Group {
+    Row(usize),
+    Column(usize),
+    Square(usize),
+}
+
+impl Group {
+    /// All rows, then all columns, then all squares
+    pub fn all() -> impl Iterator<Item = Group> {
+        (0..GRID_ROWS)
+            .map(Group::Row)
+            .chain((0..GRID_COLUMNS).map(Group::Column))
+            .chain((0..GRID_COLUMNS).map(Group::Square))
+    }
+}
+
+/// Numbered from 1, like "row 4"
+impl std::fmt::Display for Group {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Group::Row(nr) => f.write_fmt(format_args!("row {}", nr + 1)),
+            Group::Column(nr) => f.write_fmt(format_args!("column {}", nr + 1)),
+            Group::Square(nr) => f.write_fmt(format_args!("square {}", nr + 1)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Contradiction {
+    /// The cell has no value and no options left
+    NoOptions { cell_index: usize },
+    /// None of the cells in the group can hold the value anymore
+    NoPlace { group: Group, value: usize },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolutionCount {
+    Exactly(usize),
+    /// The search stopped at the requested limit
+    AtLeast(usize),
+}
+
 #[derive(Debug, PartialEq)]
 pub enum SudokuError {
-    CannotChoose { cell: Cell, value: usize },
-    InvalidCellIndex { cell_index: usize },
-    IllegalValue { value: usize },
+    CannotChoose {
+        cell: Cell,
+        value: usize,
+    },
+    InvalidCellIndex {
+        cell_index: usize,
+    },
+    IllegalValue {
+        value: usize,
+    },
+    /// Parse errors report 1-based line and column positions
+    UnexpectedCharacter {
+        line: usize,
+        column: usize,
+        character: char,
+    },
+    TooManyCells {
+        line: usize,
+        column: usize,
+    },
+    /// The text ended early, so there is no position to point at; the count of cells read
+    /// tells how far it got
+    TooFewCells {
+        count: usize,
+    },
+    ConflictingGiven {
+        line: usize,
+        column: usize,
+        value: usize,
+    },
+    /// The given leaves no way to fill in the grid, through the values it leaves as the last
+    /// option of their cells
+    ContradictingGiven {
+        line: usize,
+        column: usize,
+        value: usize,
+        contradiction: Contradiction,
+    },
+    Contradiction(Contradiction),
+    NoSolution,
+    /// The step only places values already there and eliminates options already gone
+    StepWithoutEffect {
+        technique: &'static str,
+    },
 }
@@ -0,0 +1,208 @@
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+use crate::GRID_COLUMNS;
+
+/// The values still possible for a cell, or for a group of cells, as a bitset
+/// where bit n is set when value n is available. Values outside 1..=9 are never
+/// available: single, insert and remove ignore them.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Candidates(u16);
+
+impl Candidates {
+    pub const NONE: Candidates = Candidates(0);
+    pub const ALL: Candidates = Candidates(((1 << GRID_COLUMNS) - 1) << 1);
+
+    pub fn single(value: usize) -> Self {
+        Candidates(bit(value))
+    }
+
+    /// Bypasses the checks on the values, to make candidates that can't otherwise exist
+    #[cfg(test)]
+    pub(crate) fn from_bits(bits: u16) -> Self {
+        Candidates(bits)
+    }
+
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+
+    pub fn contains(self, value: usize) -> bool {
+        self.0 & bit(value) != 0
+    }
+
+    pub fn insert(&mut self, value: usize) {
+        self.0 |= bit(value);
+    }
+
+    /// Returns whether the value was present
+    pub fn remove(&mut self, value: usize) -> bool {
+        let present = self.contains(value);
+        self.0 &= !bit(value);
+        present
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The lowest value available
+    pub fn first(self) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.0.trailing_zeros() as usize)
+        }
+    }
+
+    /// The value, when it is the only one available
+    pub fn single_value(self) -> Option<usize> {
+        if self.len() == 1 {
+            self.first()
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(self) -> CandidatesIter {
+        CandidatesIter(self.0)
+    }
+}
+
+/// The bit of the value, or none outside 1..=9
+fn bit(value: usize) -> u16 {
+    if (1..=GRID_COLUMNS).contains(&value) {
+        1 << value
+    } else {
+        0
+    }
+}
+
+impl BitOr for Candidates {
+    type Output = Candidates;
+
+    fn bitor(self, other: Self) -> Self {
+        Candidates(self.0 | other.0)
+    }
+}
+
+impl BitAnd for Candidates {
+    type Output = Candidates;
+
+    fn bitand(self, other: Self) -> Self {
+        Candidates(self.0 & other.0)
+    }
+}
+
+impl Sub for Candidates {
+    type Output = Candidates;
+
+    fn sub(self, other: Self) -> Self {
+        Candidates(self.0 & !other.0)
+    }
+}
+
+impl Not for Candidates {
+    type Output = Candidates;
+
+    fn not(self) -> Self {
+        Candidates::ALL - self
+    }
+}
+
+impl From<usize> for Candidates {
+    fn from(value: usize) -> Self {
+        Candidates::single(value)
+    }
+}
+
+impl FromIterator<usize> for Candidates {
+    fn from_iter<I: IntoIterator<Item = usize>>(values: I) -> Self {
+        let mut candidates = Candidates::NONE;
+        values
+            .into_iter()
+            .for_each(|value| candidates.insert(value));
+        candidates
+    }
+}
+
+impl IntoIterator for Candidates {
+    type Item = usize;
+    type IntoIter = CandidatesIter;
+
+    fn into_iter(self) -> CandidatesIter {
+        self.iter()
+    }
+}
+
+pub struct CandidatesIter(u16);
+
+impl Iterator for CandidatesIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            None
+        } else {
+            let value = self.0.trailing_zeros() as usize;
+            self.0 &= self.0 - 1;
+            Some(value)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl std::fmt::Debug for Candidates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl std::fmt::Display for Candidates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<String> = self.iter().map(|value| format!("{}", value)).collect();
+        f.write_fmt(format_args!("({})", values.join(",")))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn candidate_operations() {
+    let mut candidates: Candidates = [1, 4, 7, 9].into_iter().collect();
+
+    assert_eq!(candidates.len(), 4);
+    assert_eq!(candidates.first(), Some(1));
+    assert!(candidates.remove(1));
+    assert!(!candidates.remove(1));
+    assert_eq!(candidates.iter().collect::<Vec<usize>>(), vec![4, 7, 9]);
+    assert_eq!(format!("{}", candidates), "(4,7,9)");
+
+    let others = Candidates::single(4) | Candidates::single(5);
+    assert_eq!(candidates & others, Candidates::single(4));
+    assert_eq!((candidates - others).len(), 2);
+    assert_eq!((!others).len(), 7);
+    assert_eq!(Candidates::ALL.len(), 9);
+    assert_eq!(Candidates::single(6).single_value(), Some(6));
+    assert_eq!(Candidates::NONE.first(), None);
+}
+
+#[cfg(test)]
+#[test]
+fn values_out_of_range() {
+    let mut candidates = Candidates::single(16);
+    assert!(candidates.is_empty());
+
+    candidates.insert(0);
+    candidates.insert(12);
+    assert!(candidates.is_empty());
+    assert!(!Candidates::ALL.contains(10));
+    assert!(!candidates.remove(20));
+    assert_eq!([3, 15].into_iter().collect::<Candidates>(), 3.into());
+}
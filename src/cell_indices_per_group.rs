@@ -1,15 +1,30 @@
-use crate::{GRID_COLUMNS, GRID_INDEX_MAX, GRID_ROWS, GRID_SQUARE_SIDE};
+use std::sync::OnceLock;
+
+use crate::{Group, GRID_COLUMNS, GRID_INDEX_MAX, GRID_ROWS, GRID_SIZE, GRID_SQUARE_SIDE};
+
+pub const PEER_COUNT: usize = 3 * (GRID_COLUMNS - 1) - 2 * (GRID_SQUARE_SIDE - 1);
 
 type Rows = [[usize; GRID_COLUMNS]; GRID_ROWS];
 type Columns = [[usize; GRID_ROWS]; GRID_COLUMNS];
 type Squares = [[usize; GRID_COLUMNS]; GRID_COLUMNS];
+/// The other cells in the same row, column or square
+type Peers = [[usize; PEER_COUNT]; GRID_SIZE];
+/// Bit n is set when cell index n is a peer
+type PeerMasks = [u128; GRID_SIZE];
+/// Row, column and square per cell
+type CellGroups = [[Group; 3]; GRID_SIZE];
+
+static SHARED: OnceLock<CellIndices> = OnceLock::new();
 
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct CellIndices {
     rows: Rows,
     columns: Columns,
     squares: Squares,
+    peers: Peers,
+    peer_masks: PeerMasks,
+    cell_groups: CellGroups,
 }
 
 impl CellIndices {
@@ -17,6 +32,11 @@ impl CellIndices {
         Self::default()
     }
 
+    /// The tables never change, so all puzzles refer to a single instance
+    pub fn shared() -> &'static Self {
+        SHARED.get_or_init(Self::new)
+    }
+
     #[allow(dead_code)]
     pub fn for_row(&self, row_nr: usize) -> &[usize] {
         &self.rows[row_nr]
@@ -31,6 +51,33 @@ impl CellIndices {
     pub fn for_square(&self, square_nr: usize) -> &[usize] {
         &self.squares[square_nr]
     }
+
+    pub fn peers(&self, cell_index: usize) -> &[usize] {
+        &self.peers[cell_index]
+    }
+
+    #[allow(dead_code)]
+    pub fn are_peers(&self, cell_index: usize, other_index: usize) -> bool {
+        self.peer_masks[cell_index] & (1 << other_index) != 0
+    }
+
+    /// Bit n is set when cell n is a peer of the cell
+    pub fn peer_mask(&self, cell_index: usize) -> u128 {
+        self.peer_masks[cell_index]
+    }
+
+    /// The row, column and square the cell belongs to
+    pub fn groups_of(&self, cell_index: usize) -> &[Group; 3] {
+        &self.cell_groups[cell_index]
+    }
+
+    pub fn for_group(&self, group: Group) -> &[usize] {
+        match group {
+            Group::Row(row_nr) => self.for_row(row_nr),
+            Group::Column(column_nr) => self.for_column(column_nr),
+            Group::Square(square_nr) => self.for_square(square_nr),
+        }
+    }
 }
 
 impl Default for CellIndices {
@@ -43,11 +90,19 @@ impl Default for CellIndices {
         fill_columns(&mut columns);
         fill_squares(&mut squares);
 
-        Self {
+        let mut cell_indices = Self {
             rows,
             columns,
             squares,
-        }
+            peers: [[0; PEER_COUNT]; GRID_SIZE],
+            peer_masks: [0; GRID_SIZE],
+            cell_groups: [[Group::Row(0); 3]; GRID_SIZE],
+        };
+
+        fill_cell_groups(&mut cell_indices);
+        fill_peers(&mut cell_indices);
+
+        cell_indices
     }
 }
 
@@ -100,9 +155,61 @@ fn fill_squares(squares: &mut Squares) {
     }
 }
 
+#[inline]
+fn fill_cell_groups(cell_indices: &mut CellIndices) {
+    for nr in 0..GRID_COLUMNS {
+        for (position, group) in [Group::Row(nr), Group::Column(nr), Group::Square(nr)]
+            .into_iter()
+            .enumerate()
+        {
+            for cell_index in cell_indices.for_group(group).to_vec() {
+                cell_indices.cell_groups[cell_index][position] = group;
+            }
+        }
+    }
+}
+
+#[inline]
+fn fill_peers(cell_indices: &mut CellIndices) {
+    for cell_index in 0..GRID_SIZE {
+        let peer_mask = cell_indices
+            .groups_of(cell_index)
+            .iter()
+            .flat_map(|group| cell_indices.for_group(*group))
+            .filter(|peer_index| **peer_index != cell_index)
+            .fold(0_u128, |mask, peer_index| mask | (1 << peer_index));
+
+        for (peer, peer_index) in cell_indices.peers[cell_index]
+            .iter_mut()
+            .zip((0..GRID_SIZE).filter(|index| peer_mask & (1 << index) != 0))
+        {
+            *peer = peer_index;
+        }
+        cell_indices.peer_masks[cell_index] = peer_mask;
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn grid_indices() {
     let grid = CellIndices::new();
-    println!("Grid {:?}", grid)
+    println!("Grid {:?}", grid.rows)
+}
+
+#[cfg(test)]
+#[test]
+fn peer_indices() {
+    let grid = CellIndices::shared();
+
+    assert_eq!(
+        grid.peers(10),
+        [0, 1, 2, 9, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 28, 37, 46, 55, 64, 73]
+    );
+    assert_eq!(
+        grid.groups_of(41),
+        &[Group::Row(4), Group::Column(5), Group::Square(4)]
+    );
+    assert!(grid.are_peers(80, 60));
+    assert!(!grid.are_peers(80, 59));
+    assert!(!grid.are_peers(80, 80));
 }
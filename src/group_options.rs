@@ -0,0 +1,72 @@
+use crate::{Candidate, Candidates, CellOrigins, Group, Sudoku};
+
+/// Collects the options of the cells in a group, remembering which cells each value can go to
+#[derive(Debug, Default)]
+pub(crate) struct GroupOptions {
+    seen: Candidates,
+    set_from: CellOrigins,
+}
+
+impl GroupOptions {
+    pub(crate) fn new() -> Self {
+        GroupOptions::default()
+    }
+
+    /// The options of the cells without value in the group
+    pub(crate) fn of(sudoku: &Sudoku, group: Group) -> Self {
+        let mut group_options = GroupOptions::new();
+
+        for cell in sudoku.cells_of(group) {
+            if cell.has_options() {
+                group_options.add(&cell.options, cell.index)
+            }
+        }
+
+        group_options
+    }
+
+    pub(crate) fn add(&mut self, other: &Candidates, cell_index: usize) {
+        self.seen = self.seen | *other;
+        other
+            .iter()
+            .for_each(|value| self.set_from[value] |= 1 << cell_index);
+    }
+
+    /// The values that are an option for at least one cell
+    pub(crate) fn values(&self) -> Candidates {
+        self.seen
+    }
+
+    /// The cells that have any of the values as option
+    pub(crate) fn cells_for_any(&self, values: Candidates) -> u128 {
+        values
+            .iter()
+            .fold(0, |cells, value| cells | self.set_from[value])
+    }
+
+    /// A value that can go to only one cell in the group
+    pub(crate) fn single_option(&self) -> Option<Candidate> {
+        self.seen.iter().find_map(|value| {
+            let cells = self.set_from[value];
+            if cells.count_ones() == 1 {
+                Some(Candidate::new(cells.trailing_zeros() as usize, value))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// The cell indices of the bits set in the mask
+pub(crate) fn indices(mask: u128) -> impl Iterator<Item = usize> {
+    let mut remaining = mask;
+    std::iter::from_fn(move || {
+        if remaining == 0 {
+            None
+        } else {
+            let index = remaining.trailing_zeros() as usize;
+            remaining &= remaining - 1;
+            Some(index)
+        }
+    })
+}
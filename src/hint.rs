@@ -0,0 +1,265 @@
+use crate::{
+    Candidate, Candidates, CellIndices, Contradiction, Evidence, Group, Step, Strategy, Sudoku,
+    SudokuError, SudokuResult, GRID_COLUMNS, GRID_SIZE, GRID_SQUARE_SIDE,
+};
+
+/// A grid as a player has it: the values filled in so far, and the pencil marks of cells
+/// without value. Unlike a parsed Sudoku, nothing gets filled in that the player didn't.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayerGrid {
+    values: [Option<usize>; GRID_SIZE],
+    marks: [Option<Candidates>; GRID_SIZE],
+}
+
+impl PlayerGrid {
+    pub fn new() -> Self {
+        PlayerGrid {
+            values: [None; GRID_SIZE],
+            marks: [None; GRID_SIZE],
+        }
+    }
+
+    pub fn set_value(&mut self, cell_index: usize, value: Option<usize>) -> SudokuResult {
+        if cell_index >= GRID_SIZE {
+            Err(SudokuError::InvalidCellIndex { cell_index })
+        } else if value.is_some_and(|value| !(1..=GRID_COLUMNS).contains(&value)) {
+            Err(SudokuError::IllegalValue {
+                value: value.unwrap_or(0),
+            })
+        } else {
+            self.values[cell_index] = value;
+            Ok(())
+        }
+    }
+
+    /// The values the player pencilled in for the cell; without marks the cell can get any
+    /// value its peers don't have
+    pub fn set_marks(&mut self, cell_index: usize, marks: Option<Candidates>) -> SudokuResult {
+        let illegal = marks.and_then(|marks| (marks - Candidates::ALL).first());
+        if cell_index >= GRID_SIZE {
+            Err(SudokuError::InvalidCellIndex { cell_index })
+        } else if let Some(value) = illegal {
+            Err(SudokuError::IllegalValue { value })
+        } else {
+            self.marks[cell_index] = marks;
+            Ok(())
+        }
+    }
+
+    /// The simplest step the player can take next, if any
+    pub fn hint(&self) -> SudokuResult<Option<Hint>> {
+        self.hint_with(&Strategy::default())
+    }
+
+    /// Like hint, with the techniques of the strategy after the naked singles
+    pub fn hint_with(&self, strategy: &Strategy) -> SudokuResult<Option<Hint>> {
+        if let Some(step) = self.naked_single()? {
+            return Ok(Some(Hint { step }));
+        }
+
+        // without naked singles, the values and marks leave no cell with a single option, so
+        // the puzzle gets exactly the options the player sees
+        let mut sudoku = Sudoku::new();
+        for (cell_index, value) in self.values.iter().enumerate() {
+            if let Some(value) = value {
+                sudoku.choose(cell_index, *value)?;
+            }
+        }
+        for (cell_index, marks) in self.marks.iter().enumerate() {
+            if let (None, Some(marks)) = (self.values[cell_index], marks) {
+                for value in (Candidates::ALL - *marks).iter() {
+                    sudoku.eliminate_option(cell_index, value)?;
+                }
+            }
+        }
+
+        Ok(strategy.find(&sudoku).map(|step| Hint { step }))
+    }
+
+    /// A cell without value with one option left
+    fn naked_single(&self) -> SudokuResult<Option<Step>> {
+        for cell_index in 0..GRID_SIZE {
+            if self.values[cell_index].is_some() {
+                continue;
+            }
+
+            let options = self.options(cell_index);
+            if let Some(value) = options.single_value() {
+                return Ok(Some(Step {
+                    technique: "Naked single",
+                    placements: vec![Candidate::new(cell_index, value)],
+                    eliminations: Vec::new(),
+                    evidence: Evidence::NakedSingle,
+                }));
+            } else if options.is_empty() {
+                return Err(SudokuError::Contradiction(Contradiction::NoOptions {
+                    cell_index,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The marks of the cell, or all values, without the values of its peers
+    fn options(&self, cell_index: usize) -> Candidates {
+        CellIndices::shared()
+            .peers(cell_index)
+            .iter()
+            .filter_map(|peer_index| self.values[*peer_index])
+            .fold(
+                self.marks[cell_index].unwrap_or(Candidates::ALL),
+                |options, value| options - value.into(),
+            )
+    }
+}
+
+impl Default for PlayerGrid {
+    fn default() -> Self {
+        PlayerGrid::new()
+    }
+}
+
+/// How much a hint gives away
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HintLevel {
+    /// Where to look
+    Region,
+    /// Where to look and what to look for
+    Technique,
+    /// The step itself
+    Move,
+}
+
+/// The next step for a player, to reveal as little of as they ask for
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hint {
+    step: Step,
+}
+
+impl Hint {
+    /// The groups that hold the pattern, or else the squares of the cells the step changes
+    pub fn region(&self) -> Vec<Group> {
+        match &self.step.evidence {
+            Evidence::HiddenSingle { group } | Evidence::Subset { group, .. } => vec![*group],
+            Evidence::Intersection { from, to, .. } => vec![*from, *to],
+            Evidence::Fish { base, .. } => base.clone(),
+            Evidence::EmptyRectangle { square, .. } => vec![*square],
+            Evidence::SueDeCoq { line, square, .. } => vec![*line, *square],
+            Evidence::AlmostLockedSets { sets, .. } => sets.iter().map(|set| set.group).collect(),
+            _ => {
+                let changed = if self.step.placements.is_empty() {
+                    &self.step.eliminations
+                } else {
+                    &self.step.placements
+                };
+                let mut squares: Vec<usize> = changed
+                    .iter()
+                    .map(|candidate| {
+                        let (row, column) = (
+                            candidate.cell_index / GRID_COLUMNS,
+                            candidate.cell_index % GRID_COLUMNS,
+                        );
+                        row / GRID_SQUARE_SIDE * GRID_SQUARE_SIDE + column / GRID_SQUARE_SIDE
+                    })
+                    .collect();
+                squares.sort();
+                squares.dedup();
+                squares.into_iter().map(Group::Square).collect()
+            }
+        }
+    }
+
+    pub fn technique(&self) -> &'static str {
+        self.step.technique
+    }
+
+    pub fn step(&self) -> &Step {
+        &self.step
+    }
+
+    /// The hint in words, giving away no more than the level
+    pub fn text(&self, level: HintLevel) -> String {
+        let region = self
+            .region()
+            .iter()
+            .map(|group| group.to_string())
+            .collect::<Vec<String>>()
+            .join(" and ");
+        match level {
+            HintLevel::Region => format!("Look at {}", region),
+            HintLevel::Technique => format!("{} in {}", self.technique(), region),
+            HintLevel::Move => self.step.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+fn player_grid(values: &[(usize, usize)]) -> SudokuResult<PlayerGrid> {
+    let mut grid = PlayerGrid::new();
+    for (cell_index, value) in values {
+        grid.set_value(*cell_index, Some(*value))?;
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+#[test]
+fn hints_from_vague_to_exact() -> SudokuResult {
+    // r1c9 can only be 9, though parsing the puzzle would have filled it in right away
+    let grid = player_grid(
+        &(0..8)
+            .map(|cell_index| (cell_index, cell_index + 1))
+            .collect::<Vec<_>>(),
+    )?;
+    let hint = grid.hint()?.expect("a naked single");
+    assert_eq!(hint.technique(), "Naked single");
+    assert_eq!(hint.text(HintLevel::Region), "Look at square 3");
+    assert_eq!(hint.text(HintLevel::Technique), "Naked single in square 3");
+    assert_eq!(hint.text(HintLevel::Move), "Naked single: 9 at r1c9");
+
+    // the 1s in the first square and in columns 6, 7 and 8 leave r2c9 for 1 in row 2
+    let grid = player_grid(&[(0, 1), (12, 7), (13, 8), (32, 1), (42, 1), (61, 1)])?;
+    let hint = grid.hint()?.expect("a hidden single");
+    assert_eq!(hint.text(HintLevel::Region), "Look at row 2");
+    assert_eq!(
+        hint.text(HintLevel::Move),
+        "Hidden single: 1 in row 2 at r2c9"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn hints_use_pencil_marks() -> SudokuResult {
+    let mut grid = PlayerGrid::new();
+    grid.set_marks(0, Some([1, 2].into_iter().collect()))?;
+    grid.set_marks(1, Some([1, 2].into_iter().collect()))?;
+
+    let hint = grid.hint()?.expect("a naked pair");
+    assert_eq!(hint.technique(), "Naked pair");
+    assert_eq!(hint.region(), vec![Group::Row(0)]);
+
+    grid.set_marks(2, Some(Candidates::NONE))?;
+    assert_eq!(
+        grid.hint(),
+        Err(SudokuError::Contradiction(Contradiction::NoOptions {
+            cell_index: 2
+        }))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn marks_out_of_range() {
+    let mut grid = PlayerGrid::new();
+    assert_eq!(
+        grid.set_marks(0, Some(Candidates::from_bits(1 << 1 | 1 << 12))),
+        Err(SudokuError::IllegalValue { value: 12 })
+    );
+    assert_eq!(
+        grid.set_marks(81, Some(Candidates::ALL)),
+        Err(SudokuError::InvalidCellIndex { cell_index: 81 })
+    );
+    assert_eq!(grid, PlayerGrid::new());
+}
@@ -1,6 +1,6 @@
 mod api;
 pub(crate) use api::*;
-pub use api::{SudokuError, SudokuResult};
+pub use api::{Contradiction, Group, SolutionCount, SudokuError, SudokuResult};
 
 mod cell_indices_per_group;
 pub(crate) use cell_indices_per_group::*;
@@ -8,6 +8,34 @@ pub(crate) use cell_indices_per_group::*;
 mod sudoku;
 pub use sudoku::Sudoku;
 
+mod parse;
+
+mod search;
+
+mod steps;
+pub use steps::Steps;
+
+mod hint;
+pub use hint::{Hint, HintLevel, PlayerGrid};
+
+mod rating;
+pub use rating::{Category, Rating};
+
+mod step;
+pub use step::{AlmostLockedSet, Branch, Candidate, Evidence, Link, Step};
+
+mod techniques;
+pub use techniques::BuiltIn;
+
+mod strategy;
+pub use strategy::{Strategy, Technique};
+
+mod group_options;
+pub(crate) use group_options::*;
+
+mod candidates;
+pub use candidates::{Candidates, CandidatesIter};
+
 mod cell;
 pub(crate) use cell::*;
 
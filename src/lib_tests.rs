@@ -1,10 +1,18 @@
 #[cfg(test)]
-use crate::{Sudoku, SudokuResult};
+use crate::{
+    BuiltIn, Category, SolutionCount, Strategy, Sudoku, SudokuError, SudokuResult, GRID_COLUMNS,
+    GRID_ROWS,
+};
 
 #[cfg(test)]
 const GELDERLANDER_0308: &str = include_str!("../examples/gelderlander-20220308.txt");
 const HEURISTIC_2_1: &str = include_str!("../examples/heuristic_2_1.txt");
 const DAILY_TELEGRAPH: &str = include_str!("../examples/daily_telegraph.txt");
+const HARD: &str = include_str!("../examples/hard.txt");
+const UNIQUE_RECTANGLE: &str =
+    "...4.8.2.13.............56.4..53.........72.87.6...9.4......8.....87.6.5.9..2....";
+const FORCING: &str =
+    "..97.......1..6...2.7.5..4......25..3....9..8.6..8.2..4..26.85.17..9...6........3";
 
 #[cfg(test)]
 #[test]
@@ -28,8 +36,8 @@ fn gelderlander_20220308() -> SudokuResult {
 fn heuristic_2_1() -> SudokuResult {
     let mut sudoku = create_sudoku(HEURISTIC_2_1);
     let iterations = sudoku.solve()?;
-    assert_eq!(iterations, 2);
-    assert_eq!(sudoku.todo_count, 38);
+    assert_eq!(iterations, 40);
+    assert_solved(&sudoku);
     println!("Heuristic_2: {}", sudoku);
     Ok(())
 }
@@ -39,11 +47,242 @@ fn heuristic_2_1() -> SudokuResult {
 fn daily_telegraph() -> SudokuResult {
     let mut sudoku = create_sudoku(DAILY_TELEGRAPH);
     let iterations = sudoku.solve()?;
+    assert_eq!(iterations, 57);
+    assert_solved(&sudoku);
+    println!("Daily Telegraph: {}", sudoku);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn daily_telegraph_singles_only() -> SudokuResult {
+    let mut sudoku = create_sudoku(DAILY_TELEGRAPH);
+    let iterations = sudoku.solve_with(&Strategy::empty().with(BuiltIn::HiddenSingle))?;
     assert_eq!(iterations, 2);
     assert_eq!(sudoku.todo_count, 55);
-    println!("Daily Telegraph: {}", sudoku);
     Ok(())
 }
+
+#[cfg(test)]
+#[test]
+fn complete_heuristic_2_1() -> SudokuResult {
+    let mut sudoku = create_sudoku(HEURISTIC_2_1);
+    let placed = sudoku.solve_complete()?;
+    assert_eq!(placed, 40);
+    assert_solved(&sudoku);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn complete_daily_telegraph() -> SudokuResult {
+    let mut sudoku = create_sudoku(DAILY_TELEGRAPH);
+    sudoku.solve_complete()?;
+    assert_solved(&sudoku);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn hard() -> SudokuResult {
+    let mut sudoku = create_sudoku(HARD);
+    let iterations = sudoku.solve()?;
+    assert_eq!(iterations, 62);
+    assert_solved(&sudoku);
+    println!("Hard: {}", sudoku);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn hard_assuming_unique() -> SudokuResult {
+    // the techniques that come before the uniqueness ones already solve it
+    let mut sudoku = create_sudoku(HARD);
+    let iterations = sudoku.solve_assuming_unique()?;
+    assert_eq!(iterations, 62);
+    assert_solved(&sudoku);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn unique_rectangle_assuming_unique() -> SudokuResult {
+    let sudoku = create_sudoku(UNIQUE_RECTANGLE);
+    let techniques = sudoku
+        .steps_with(Strategy::assuming_unique())
+        .map(|step| step.map(|step| step.technique))
+        .collect::<SudokuResult<Vec<&str>>>()?;
+    assert!(techniques.contains(&"Unique Rectangle type 1"));
+
+    let mut sudoku = create_sudoku(UNIQUE_RECTANGLE);
+    let iterations = sudoku.solve_assuming_unique()?;
+    assert_eq!(iterations, 57);
+    assert_solved(&sudoku);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn forcing_before_guessing() -> SudokuResult {
+    let mut sudoku = create_sudoku(FORCING);
+    sudoku.solve()?;
+    assert_solved(&sudoku);
+
+    let mut sudoku = create_sudoku(FORCING);
+    sudoku.solve_with(&BuiltIn::DEFAULT.into())?;
+    assert!(sudoku.todo_count > 0);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn steps_carry_technique_names() -> SudokuResult {
+    let strategy = Strategy::assuming_unique();
+    let names: Vec<&str> = strategy
+        .techniques()
+        .map(|technique| technique.name())
+        .collect();
+
+    for puzzle in [HARD, UNIQUE_RECTANGLE, FORCING] {
+        for step in create_sudoku(puzzle).steps_with(Strategy::assuming_unique()) {
+            let technique = step?.technique;
+            assert!(technique == "Naked single" || names.contains(&technique));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn rate_examples() -> SudokuResult {
+    let mut ratings = Vec::new();
+    for (name, text) in [
+        ("hard", HARD),
+        ("daily telegraph", DAILY_TELEGRAPH),
+        ("gelderlander", GELDERLANDER_0308),
+        ("heuristic 2.1", HEURISTIC_2_1),
+    ] {
+        ratings.push((create_sudoku(text).rate()?, name));
+    }
+    ratings.sort_by(|(one, _), (other, _)| one.score.total_cmp(&other.score));
+
+    let names: Vec<&str> = ratings.iter().map(|(_, name)| *name).collect();
+    assert_eq!(
+        names,
+        vec!["gelderlander", "daily telegraph", "heuristic 2.1", "hard"]
+    );
+    let (rating, _) = &ratings[0];
+    assert_eq!(rating.category, Category::Easy);
+    assert_eq!(rating.hardest, Some("Naked single"));
+    assert_eq!(rating.guesses, 0);
+    let (rating, _) = &ratings[3];
+    assert_eq!(rating.category, Category::Hard);
+    assert_eq!(rating.hardest, Some("XY-Wing"));
+    assert_eq!(rating.steps, 75);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn rate_with_guesses() -> SudokuResult {
+    // stuck right away, without a single step
+    let sudoku = create_sudoku(
+        "1.......2.9.4...5...6...7...5.9.3.......7.......85..4.7.....6...3...9.8...2.....1",
+    );
+    let rating = sudoku.rate_with(&Strategy::empty().with(BuiltIn::HiddenSingle))?;
+    assert_eq!(rating.category, Category::Expert);
+    assert_eq!(rating.steps, 0);
+    assert!(rating.guesses > 0);
+
+    // the forcing chains of the last resort save the search from guessing
+    let sudoku = create_sudoku(FORCING);
+    let rating = sudoku.rate()?;
+    assert_eq!(rating.hardest, Some("Cell forcing chain"));
+    assert_eq!(rating.guesses, 0);
+    let rating = sudoku.rate_with(&BuiltIn::DEFAULT.into())?;
+    assert_eq!(rating.score, 10.0);
+    assert!(rating.guesses > 0);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn complete_hard() -> SudokuResult {
+    let mut sudoku = create_sudoku(HARD);
+    sudoku.solve_complete()?;
+    assert_solved(&sudoku);
+    println!("Hard: {}", sudoku);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn complete_without_solution() {
+    let mut line: String = GELDERLANDER_0308.split_ascii_whitespace().collect();
+    line.replace_range(6..7, "8");
+
+    let mut sudoku = create_sudoku(&line);
+    assert_eq!(sudoku.solve_complete(), Err(SudokuError::NoSolution));
+}
+
+#[cfg(test)]
+#[test]
+fn unique_solutions() {
+    for puzzle in [GELDERLANDER_0308, HEURISTIC_2_1, DAILY_TELEGRAPH, HARD] {
+        let sudoku = create_sudoku(puzzle);
+        assert_eq!(sudoku.count_solutions(5), SolutionCount::Exactly(1));
+        assert!(sudoku.has_unique_solution());
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn multiple_solutions() {
+    let mut line: String = HARD.split_ascii_whitespace().collect();
+    line.replace_range(1..2, "_");
+
+    let sudoku = create_sudoku(&line);
+    assert_eq!(sudoku.count_solutions(3), SolutionCount::AtLeast(3));
+    assert!(!sudoku.has_unique_solution());
+
+    let solutions = sudoku.solutions(2);
+    assert_eq!(solutions.len(), 2);
+    assert_ne!(format!("{}", solutions[0]), format!("{}", solutions[1]));
+    solutions.iter().for_each(assert_solved);
+
+    assert_eq!(
+        Sudoku::new().count_solutions(10),
+        SolutionCount::AtLeast(10)
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn no_solutions() {
+    let mut line: String = GELDERLANDER_0308.split_ascii_whitespace().collect();
+    line.replace_range(6..7, "8");
+
+    let sudoku = create_sudoku(&line);
+    assert_eq!(sudoku.count_solutions(2), SolutionCount::Exactly(0));
+    assert!(sudoku.solutions(2).is_empty());
+}
+
+#[cfg(test)]
+#[test]
+fn count_solutions_without_limit() {
+    let mut line: String = GELDERLANDER_0308.split_ascii_whitespace().collect();
+    line.replace_range(6..7, "8");
+
+    assert_eq!(
+        create_sudoku(&line).count_solutions(0),
+        SolutionCount::Exactly(0)
+    );
+    assert_eq!(
+        create_sudoku(HARD).count_solutions(0),
+        SolutionCount::AtLeast(1)
+    );
+}
+
 /*
 
 #[cfg(test)]
@@ -136,23 +375,37 @@ fn assert_indices_eq(
 
 #[cfg(test)]
 fn create_sudoku(text: &str) -> Sudoku {
-    let mut sudoku = Sudoku::new();
-
-    for (index, value) in text
-        .lines()
-        .flat_map(|line| line.split_ascii_whitespace())
-        .enumerate()
-        .filter(|(_, value)| "_".ne(*value))
-    {
-        if let Ok(num_value) = value.parse() {
-            let result = sudoku.choose(index, num_value);
-            if result.is_err() {
-                assert!(false, "got choose error: {:?}", result);
+    match Sudoku::parse(text) {
+        Ok(sudoku) => sudoku,
+        Err(error) => panic!("got parse error: {:?}", error),
+    }
+}
+
+#[cfg(test)]
+fn assert_solved(sudoku: &Sudoku) {
+    assert_eq!(sudoku.todo_count, 0, "Not all cells filled");
+
+    for row in 1..=GRID_ROWS {
+        for column in 1..=GRID_COLUMNS {
+            let value = sudoku.cell_at(row, column).value;
+            let square = ((row - 1) / 3 * 3 + 1, (column - 1) / 3 * 3 + 1);
+
+            for other in 1..=GRID_COLUMNS {
+                let square_cell = (square.0 + (other - 1) / 3, square.1 + (other - 1) % 3);
+                for (other_row, other_column) in [(row, other), (other, column), square_cell] {
+                    if (other_row, other_column) != (row, column) {
+                        assert_ne!(
+                            sudoku.cell_at(other_row, other_column).value,
+                            value,
+                            "r{}c{} repeats r{}c{}",
+                            other_row,
+                            other_column,
+                            row,
+                            column
+                        );
+                    }
+                }
             }
-        } else {
-            assert!(false, "Could not parse {}", value);
         }
     }
-
-    sudoku
 }
@@ -0,0 +1,139 @@
+use std::str::FromStr;
+
+use crate::{Sudoku, SudokuError, SudokuResult, GRID_SIZE};
+
+#[cfg(test)]
+use crate::{Contradiction, Group};
+
+impl Sudoku {
+    /// Reads a puzzle either as a grid of whitespace separated tokens (see examples/*.txt)
+    /// or as a single line of 81 characters. Blanks are written as `_`, `.` or `0`.
+    pub fn parse(text: &str) -> SudokuResult<Self> {
+        let mut sudoku = Sudoku::new();
+        let mut cell_index = 0_usize;
+
+        for (line_nr, line) in text.lines().enumerate() {
+            for (column_nr, character) in line.chars().enumerate() {
+                let (line, column) = (line_nr + 1, column_nr + 1);
+
+                let value = match character {
+                    c if c.is_whitespace() => continue,
+                    '_' | '.' | '0' => None,
+                    '1'..='9' => character.to_digit(10).map(|digit| digit as usize),
+                    _ => {
+                        return Err(SudokuError::UnexpectedCharacter {
+                            line,
+                            column,
+                            character,
+                        })
+                    }
+                };
+
+                if cell_index >= GRID_SIZE {
+                    return Err(SudokuError::TooManyCells { line, column });
+                }
+
+                if let Some(value) = value {
+                    sudoku
+                        .choose(cell_index, value)
+                        .map_err(|error| match error {
+                            SudokuError::CannotChoose { .. } => SudokuError::ConflictingGiven {
+                                line,
+                                column,
+                                value,
+                            },
+                            SudokuError::Contradiction(contradiction) => {
+                                SudokuError::ContradictingGiven {
+                                    line,
+                                    column,
+                                    value,
+                                    contradiction,
+                                }
+                            }
+                            error => error,
+                        })?;
+                }
+
+                cell_index += 1;
+            }
+        }
+
+        if cell_index < GRID_SIZE {
+            Err(SudokuError::TooFewCells { count: cell_index })
+        } else {
+            Ok(sudoku)
+        }
+    }
+}
+
+impl FromStr for Sudoku {
+    type Err = SudokuError;
+
+    fn from_str(text: &str) -> SudokuResult<Self> {
+        Sudoku::parse(text)
+    }
+}
+
+#[cfg(test)]
+const SINGLE_LINE: &str =
+    "_9__2__4___14________593___1_8__5___75______2_2_93_78___2___9_64_____3____7_6____";
+
+#[cfg(test)]
+#[test]
+fn single_line_matches_grid() -> SudokuResult {
+    let grid = Sudoku::parse(include_str!("../examples/gelderlander-20220308.txt"))?;
+    let dotted: Sudoku = SINGLE_LINE.replace('_', ".").parse()?;
+    let zeroes: Sudoku = SINGLE_LINE.replace('_', "0").parse()?;
+
+    assert_eq!(grid.todo_count, 46);
+    assert_eq!(format!("{}", dotted), format!("{}", grid));
+    assert_eq!(format!("{}", zeroes), format!("{}", grid));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        Sudoku::parse("1 2 x").err(),
+        Some(SudokuError::UnexpectedCharacter {
+            line: 1,
+            column: 5,
+            character: 'x'
+        })
+    );
+    assert_eq!(
+        Sudoku::parse("123\n456").err(),
+        Some(SudokuError::TooFewCells { count: 6 })
+    );
+    assert_eq!(
+        format!("{}.", SINGLE_LINE).parse::<Sudoku>().err(),
+        Some(SudokuError::TooManyCells {
+            line: 1,
+            column: 82
+        })
+    );
+    assert_eq!(
+        format!("99{}", &SINGLE_LINE[2..]).parse::<Sudoku>().err(),
+        Some(SudokuError::ConflictingGiven {
+            line: 1,
+            column: 2,
+            value: 9
+        })
+    );
+    // the 9s in rows 2 to 9 cover columns 1 to 8, and r1c9 holds a 5
+    assert_eq!(
+        "........59...........9......9...........9..........9....9...........9..........9."
+            .parse::<Sudoku>()
+            .err(),
+        Some(SudokuError::ContradictingGiven {
+            line: 1,
+            column: 80,
+            value: 9,
+            contradiction: Contradiction::NoPlace {
+                group: Group::Row(0),
+                value: 9
+            }
+        })
+    );
+}
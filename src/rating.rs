@@ -0,0 +1,101 @@
+use crate::{Strategy, Sudoku, SudokuError, SudokuResult};
+
+/// The values left as the last option of their cell, which propagation places along the way
+const NAKED_SINGLE: f32 = 2.3;
+/// Past what the techniques can do, where only guessing helps
+const GUESSING: f32 = 10.0;
+
+/// Roughly how Sudoku Explainer ratings get grouped
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    /// Singles only
+    Easy,
+    /// Up to the triples and the basic fish
+    Medium,
+    /// Up to the wings, the finned fish and coloring
+    Hard,
+    /// Chains, almost locked sets, forcing chains and guessing
+    Expert,
+}
+
+impl Category {
+    fn of(score: f32) -> Self {
+        if score <= NAKED_SINGLE {
+            Category::Easy
+        } else if score <= 4.0 {
+            Category::Medium
+        } else if score <= 6.0 {
+            Category::Hard
+        } else {
+            Category::Expert
+        }
+    }
+}
+
+/// How hard a puzzle is for a person
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rating {
+    /// The difficulty of the hardest step, on the scale of Sudoku Explainer
+    pub score: f32,
+    pub category: Category,
+    /// The technique of the hardest step the techniques took, if they took any
+    pub hardest: Option<&'static str>,
+    /// The steps until solved or stuck, each naked single counting as one
+    pub steps: usize,
+    /// The values the search guessed once the techniques got stuck
+    pub guesses: usize,
+}
+
+impl Sudoku {
+    /// Rates a copy of the puzzle, solving it with the built-in techniques
+    pub fn rate(&self) -> SudokuResult<Rating> {
+        self.rate_with(&Strategy::default())
+    }
+
+    /// Rates a copy of the puzzle, each time applying the step of the first technique in the
+    /// strategy that finds one. Values placed while the puzzle was read don't count.
+    pub fn rate_with(&self, strategy: &Strategy) -> SudokuResult<Rating> {
+        let mut sudoku = self.clone();
+        let mut score: f32 = 0.0;
+        let mut hardest = None;
+        let mut steps = 0;
+
+        while let Some((technique, step)) = strategy.find_with_technique(&sudoku) {
+            let mut placed = Vec::new();
+            sudoku.apply_step_traced(&step, Some(&mut placed))?;
+            let naked_singles = placed
+                .iter()
+                .filter(|placement| !step.placements.contains(placement))
+                .count();
+
+            steps += 1 + naked_singles;
+            if technique.difficulty() > score {
+                score = technique.difficulty();
+                hardest = Some(technique.name());
+            }
+            if naked_singles > 0 && NAKED_SINGLE > score {
+                score = NAKED_SINGLE;
+                hardest = Some("Naked single");
+            }
+        }
+
+        let guesses = if sudoku.todo_count == 0 {
+            0
+        } else {
+            let (solutions, guesses) = sudoku.solutions_and_guesses(1);
+            if solutions.is_empty() {
+                return Err(SudokuError::NoSolution);
+            }
+            score = score.max(GUESSING);
+            guesses
+        };
+
+        Ok(Rating {
+            score,
+            category: Category::of(score),
+            hardest,
+            steps,
+            guesses,
+        })
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{SolutionCount, Sudoku, SudokuError, SudokuResult};
+
+impl Sudoku {
+    /// Like solve, but when the techniques get stuck a depth-first search guesses values
+    /// for the cell with the fewest options left, until the grid is completely filled.
+    pub fn solve_complete(&mut self) -> SudokuResult<usize> {
+        let todo_count = self.todo_count;
+
+        let mut solving = self.clone();
+        match solving.solve() {
+            Ok(_) => (),
+            Err(SudokuError::Contradiction(_)) => return Err(SudokuError::NoSolution),
+            Err(error) => return Err(error),
+        }
+        match solving.solutions(1).pop() {
+            Some(solved) => {
+                *self = solved;
+                Ok(todo_count)
+            }
+            None => Err(SudokuError::NoSolution),
+        }
+    }
+
+    /// Searches for at most limit distinct solutions, leaving this puzzle untouched
+    pub fn solutions(&self, limit: usize) -> Vec<Sudoku> {
+        self.solutions_and_guesses(limit).0
+    }
+
+    /// Like solutions, along with the number of values the search guessed
+    pub(crate) fn solutions_and_guesses(&self, limit: usize) -> (Vec<Sudoku>, usize) {
+        let mut solutions = Vec::new();
+        let mut guesses = 0;
+        if limit > 0 {
+            self.clone().search(&mut solutions, limit, &mut guesses);
+        }
+        (solutions, guesses)
+    }
+
+    /// Counts up to limit solutions; a limit of 0 counts as 1, as there is nothing to tell
+    /// without looking for at least one
+    pub fn count_solutions(&self, limit: usize) -> SolutionCount {
+        let limit = limit.max(1);
+        let count = self.solutions(limit).len();
+        if count < limit {
+            SolutionCount::Exactly(count)
+        } else {
+            SolutionCount::AtLeast(count)
+        }
+    }
+
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == SolutionCount::Exactly(1)
+    }
+
+    /// Returns true when enough solutions have been collected. Only singles are applied between
+    /// guesses, as the other techniques cost more time than the guesses they save.
+    fn search(mut self, solutions: &mut Vec<Sudoku>, limit: usize, guesses: &mut usize) -> bool {
+        if self.solve_singles().is_err() {
+            return false;
+        }
+
+        let cell = match self.most_constrained_cell() {
+            Some(cell) => *cell,
+            None => {
+                solutions.push(self);
+                return solutions.len() >= limit;
+            }
+        };
+
+        cell.available_options().any(|value| {
+            *guesses += 1;
+            let mut guess = self.clone();
+            guess.choose(cell.index, value).is_ok() && guess.search(solutions, limit, guesses)
+        })
+    }
+}
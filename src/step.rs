@@ -0,0 +1,393 @@
+use crate::{Candidates, Contradiction, Group, GRID_COLUMNS};
+
+/// A value in a cell, either to place or to eliminate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Candidate {
+    pub cell_index: usize,
+    pub value: usize,
+}
+
+impl Candidate {
+    pub fn new(cell_index: usize, value: usize) -> Self {
+        Self { cell_index, value }
+    }
+}
+
+/// Two candidates of which at least one is true when the link is strong, and at most one
+/// when it is weak
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Link {
+    pub from: Candidate,
+    pub to: Candidate,
+    pub strong: bool,
+}
+
+impl Link {
+    pub fn new(from: Candidate, to: Candidate, strong: bool) -> Self {
+        Self { from, to, strong }
+    }
+}
+
+/// Cells in a group with one option more than there are cells
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlmostLockedSet {
+    pub group: Group,
+    pub cells: Vec<usize>,
+    pub values: Candidates,
+}
+
+/// What placing the assumption leads to through singles: the values placed, in cell order,
+/// and the contradiction it ran into, if any
+#[derive(Clone, Debug, PartialEq)]
+pub struct Branch {
+    pub assumption: Candidate,
+    pub placements: Vec<Candidate>,
+    pub contradiction: Option<Contradiction>,
+}
+
+/// The outcome of a technique: what to place and eliminate, and the pattern that justifies it
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step {
+    /// The name of the technique that found the step, as Technique::name gives it; steps
+    /// from propagation are called "Naked single"
+    pub technique: &'static str,
+    pub placements: Vec<Candidate>,
+    pub eliminations: Vec<Candidate>,
+    pub evidence: Evidence,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Evidence {
+    /// The placed cell is the only one in the group that can get the value
+    HiddenSingle { group: Group },
+    /// The placed value is the last option of the cell
+    NakedSingle,
+    /// The cells in the group that together hold exactly the values
+    Subset {
+        group: Group,
+        cells: Vec<usize>,
+        values: Candidates,
+    },
+    /// The options for the value in the from group all lie in the cells shared with the to group
+    Intersection {
+        value: usize,
+        from: Group,
+        to: Group,
+        cells: Vec<usize>,
+    },
+    /// Every base group has the value in a cover group, so the covers lose it everywhere else.
+    /// With fins, only the cells that also see all fins lose it.
+    Fish {
+        value: usize,
+        base: Vec<Group>,
+        cover: Vec<Group>,
+        fins: Vec<usize>,
+    },
+    /// Whatever value the pivot gets, one of the pincers gets the value
+    Wing {
+        pivot: usize,
+        pincers: Vec<usize>,
+        value: usize,
+    },
+    /// Alternating strong and weak links: the value is true at the start or at the end
+    Chain { links: Vec<Link> },
+    /// The options in the square lie on the row and column; the link ends in one of them
+    EmptyRectangle {
+        value: usize,
+        square: Group,
+        row: Group,
+        column: Group,
+        link: Link,
+    },
+    /// The strong link makes one of the two cells, with the same two options, get the value
+    WWing {
+        cells: Vec<usize>,
+        link: Link,
+        value: usize,
+    },
+    /// Per cluster, the cells linked by strong links on the value, split in two colors: in each
+    /// cluster, either all cells of the first color get the value or all of the second
+    Coloring {
+        value: usize,
+        clusters: Vec<[Vec<usize>; 2]>,
+    },
+    /// Without the extra options, the rectangle's cells could swap the values for a second
+    /// solution. Type 3 combines the extras with the subset cells into a naked subset.
+    UniqueRectangle {
+        cells: Vec<usize>,
+        values: Candidates,
+        subset: Vec<usize>,
+    },
+    /// Every other open cell has two options, which would allow a second solution unless the
+    /// cell gets the placed value
+    BivalueUniversalGrave { cell_index: usize },
+    /// Almost locked sets linked by restricted common values, which at most one of two linked
+    /// sets gets. For a death blossom, the restricted values are the options of the stem cell,
+    /// one per set.
+    AlmostLockedSets {
+        sets: Vec<AlmostLockedSet>,
+        restricted: Vec<usize>,
+        stem: Option<usize>,
+    },
+    /// The cells where the line crosses the square, with the line cells and square cells, hold
+    /// as many values as there are cells
+    SueDeCoq {
+        line: Group,
+        square: Group,
+        cells: Vec<usize>,
+        line_cells: Vec<usize>,
+        square_cells: Vec<usize>,
+    },
+    /// The branches of a forcing chain: either one ran into a contradiction, or all of them
+    /// lead to the step's placements or eliminations
+    Forcing { branches: Vec<Branch> },
+    /// The base cells' values both end up in the target cells
+    Exocet {
+        base: Vec<usize>,
+        targets: Vec<usize>,
+        values: Candidates,
+    },
+    /// Found by a technique from outside the crate, which explains itself
+    Custom { description: String },
+}
+
+/// Reads like "Hidden single: 7 in row 4 at r4c6", or for other techniques the pattern followed
+/// by what the step places and removes
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}: ", self.technique))?;
+
+        let placements = self
+            .placements
+            .iter()
+            .map(|placement| format!("{} at {}", placement.value, cell_name(placement.cell_index)))
+            .collect::<Vec<String>>()
+            .join(", ");
+        match &self.evidence {
+            Evidence::HiddenSingle { group } => {
+                let placement = self.placements.first().map_or(String::new(), |placement| {
+                    format!(
+                        "{} in {} at {}",
+                        placement.value,
+                        group,
+                        cell_name(placement.cell_index)
+                    )
+                });
+                return f.write_str(&placement);
+            }
+            Evidence::NakedSingle => return f.write_str(&placements),
+            evidence => f.write_str(&pattern(evidence))?,
+        }
+
+        if !placements.is_empty() {
+            f.write_fmt(format_args!("; places {}", placements))?;
+        }
+        if !self.eliminations.is_empty() {
+            let mut values: Vec<usize> = self
+                .eliminations
+                .iter()
+                .map(|elimination| elimination.value)
+                .collect();
+            values.sort();
+            values.dedup();
+            let removals: Vec<String> = values
+                .into_iter()
+                .map(|value| {
+                    let cells: Vec<usize> = self
+                        .eliminations
+                        .iter()
+                        .filter(|elimination| elimination.value == value)
+                        .map(|elimination| elimination.cell_index)
+                        .collect();
+                    format!("{} from {}", value, cell_names(&cells))
+                })
+                .collect();
+            f.write_fmt(format_args!("; removes {}", removals.join(" and ")))?;
+        }
+        Ok(())
+    }
+}
+
+/// The cells and groups that make up the pattern, in a few words
+fn pattern(evidence: &Evidence) -> String {
+    match evidence {
+        Evidence::HiddenSingle { group } => format!("in {}", group),
+        Evidence::NakedSingle => "last option".to_string(),
+        Evidence::Subset {
+            group,
+            cells,
+            values,
+        } => format!("{} in {} of {}", values, cell_names(cells), group),
+        Evidence::Intersection {
+            value, from, to, ..
+        } => format!("{} in {} only in {}", value, from, to),
+        Evidence::Fish {
+            value,
+            base,
+            cover,
+            fins,
+        } => {
+            let groups = |groups: &[Group]| {
+                groups
+                    .iter()
+                    .map(|group| group.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            };
+            let mut text = format!("{} in {} covered by {}", value, groups(base), groups(cover));
+            if !fins.is_empty() {
+                text += &format!(" with fins {}", cell_names(fins));
+            }
+            text
+        }
+        Evidence::Wing {
+            pivot,
+            pincers,
+            value,
+        } => format!(
+            "pivot {} with pincers {} on {}",
+            cell_name(*pivot),
+            cell_names(pincers),
+            value
+        ),
+        Evidence::Chain { links } => chain(links),
+        Evidence::EmptyRectangle {
+            value,
+            square,
+            row,
+            column,
+            link,
+        } => format!(
+            "{} in {} on {} and {}, with {}",
+            value,
+            square,
+            row,
+            column,
+            chain(&[*link])
+        ),
+        Evidence::WWing { cells, link, value } => format!(
+            "{} linked by {} on {}",
+            cell_names(cells),
+            chain(&[*link]),
+            value
+        ),
+        Evidence::Coloring { value, clusters } => {
+            let clusters: Vec<String> = clusters
+                .iter()
+                .map(|[one, other]| format!("{} against {}", cell_names(one), cell_names(other)))
+                .collect();
+            format!("{} colored {}", value, clusters.join(" and "))
+        }
+        Evidence::UniqueRectangle {
+            cells,
+            values,
+            subset,
+        } => {
+            let mut text = format!("{} in {}", values, cell_names(cells));
+            if !subset.is_empty() {
+                text += &format!(" with {}", cell_names(subset));
+            }
+            text
+        }
+        Evidence::BivalueUniversalGrave { cell_index } => {
+            format!(
+                "every open cell but {} has two options",
+                cell_name(*cell_index)
+            )
+        }
+        Evidence::AlmostLockedSets {
+            sets,
+            restricted,
+            stem,
+        } => {
+            let sets: Vec<String> = sets
+                .iter()
+                .map(|set| format!("{} in {}", set.values, cell_names(&set.cells)))
+                .collect();
+            let restricted: Vec<String> =
+                restricted.iter().map(|value| value.to_string()).collect();
+            let stem = stem.map_or(String::new(), |stem| format!("stem {}, ", cell_name(stem)));
+            format!(
+                "{}{} restricted on {}",
+                stem,
+                sets.join(" and "),
+                restricted.join(", ")
+            )
+        }
+        Evidence::SueDeCoq {
+            line,
+            square,
+            cells,
+            line_cells,
+            square_cells,
+        } => format!(
+            "{} where {} crosses {}, with {} and {}",
+            cell_names(cells),
+            line,
+            square,
+            cell_names(line_cells),
+            cell_names(square_cells)
+        ),
+        Evidence::Forcing { branches } => {
+            let branches: Vec<String> = branches
+                .iter()
+                .map(|branch| {
+                    let assumption = candidate_name(branch.assumption);
+                    if branch.contradiction.is_some() {
+                        format!("{} fails", assumption)
+                    } else {
+                        assumption
+                    }
+                })
+                .collect();
+            format!("assuming {}", branches.join(" or "))
+        }
+        Evidence::Exocet {
+            base,
+            targets,
+            values,
+        } => format!(
+            "base {} with {}, targets {}",
+            cell_names(base),
+            values,
+            cell_names(targets)
+        ),
+        Evidence::Custom { description } => description.clone(),
+    }
+}
+
+/// Strong links as "=", weak ones as "-"
+fn chain(links: &[Link]) -> String {
+    links
+        .iter()
+        .enumerate()
+        .fold(String::new(), |text, (nr, link)| {
+            let start = if nr == 0 {
+                candidate_name(link.from)
+            } else {
+                String::new()
+            };
+            let sign = if link.strong { "=" } else { "-" };
+            format!("{}{} {} {}", text, start, sign, candidate_name(link.to))
+        })
+}
+
+fn candidate_name(candidate: Candidate) -> String {
+    format!("({}){}", candidate.value, cell_name(candidate.cell_index))
+}
+
+fn cell_name(cell_index: usize) -> String {
+    format!(
+        "r{}c{}",
+        cell_index / GRID_COLUMNS + 1,
+        cell_index % GRID_COLUMNS + 1
+    )
+}
+
+fn cell_names(cells: &[usize]) -> String {
+    cells
+        .iter()
+        .map(|cell_index| cell_name(*cell_index))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -0,0 +1,104 @@
+use std::collections::VecDeque;
+
+use crate::{Candidate, Evidence, Step, Strategy, Sudoku, SudokuResult};
+
+impl Sudoku {
+    /// The steps solve takes, one at a time, on a copy of the puzzle. Values that became the
+    /// last option of their cell show up as naked singles right after the step that caused
+    /// them; the ones that did while the puzzle was read are already placed.
+    pub fn steps(&self) -> Steps {
+        self.steps_with(Strategy::default())
+    }
+
+    /// Like steps, with the techniques of the strategy
+    pub fn steps_with(&self, strategy: Strategy) -> Steps {
+        Steps {
+            sudoku: self.clone(),
+            strategy,
+            naked_singles: VecDeque::new(),
+            failed: false,
+        }
+    }
+}
+
+/// Applies each step before handing it out, and ends when the strategy finds no more steps or
+/// after a step runs into a contradiction
+pub struct Steps {
+    sudoku: Sudoku,
+    strategy: Strategy,
+    naked_singles: VecDeque<Candidate>,
+    failed: bool,
+}
+
+impl Steps {
+    /// The puzzle with the steps so far applied
+    pub fn sudoku(&self) -> &Sudoku {
+        &self.sudoku
+    }
+}
+
+impl Iterator for Steps {
+    type Item = SudokuResult<Step>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(placement) = self.naked_singles.pop_front() {
+            return Some(Ok(Step {
+                technique: "Naked single",
+                placements: vec![placement],
+                eliminations: Vec::new(),
+                evidence: Evidence::NakedSingle,
+            }));
+        }
+        if self.failed {
+            return None;
+        }
+
+        let step = self.strategy.find(&self.sudoku)?;
+        let mut placed = Vec::new();
+        match self.sudoku.apply_step_traced(&step, Some(&mut placed)) {
+            Ok(_) => {
+                self.naked_singles.extend(
+                    placed
+                        .into_iter()
+                        .filter(|placement| !step.placements.contains(placement)),
+                );
+                Some(Ok(step))
+            }
+            Err(error) => {
+                self.failed = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn steps_place_every_value() -> SudokuResult {
+    let sudoku = Sudoku::parse(include_str!("../examples/hard.txt"))?;
+    let mut steps = sudoku.steps();
+
+    let first: Vec<String> = steps
+        .by_ref()
+        .take(2)
+        .map(|step| step.map(|step| step.to_string()))
+        .collect::<SudokuResult<Vec<String>>>()?;
+    assert_eq!(
+        first,
+        vec![
+            "Hidden single: 6 in square 1 at r1c3",
+            "Pointing: 3 in square 1 only in column 1; removes 3 from r5c1, r7c1, r9c1",
+        ]
+    );
+
+    let rest = steps.by_ref().collect::<SudokuResult<Vec<Step>>>()?;
+    assert!(rest
+        .iter()
+        .any(|step| step.evidence == Evidence::NakedSingle));
+    assert_eq!(
+        1 + rest.iter().map(|step| step.placements.len()).sum::<usize>(),
+        sudoku.todo_count
+    );
+    assert_eq!(steps.sudoku().todo_count, 0);
+    Ok(())
+}
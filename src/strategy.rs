@@ -0,0 +1,182 @@
+use crate::{BuiltIn, Step, Sudoku};
+
+/// A way to find the next step in a puzzle. Implement it to have solving use techniques of
+/// your own next to the built-in ones.
+pub trait Technique {
+    fn name(&self) -> &'static str;
+
+    /// How hard the technique is for a person, on the scale of Sudoku Explainer: from 1.0 for
+    /// the last empty cell of a group to around 10 for the hardest puzzles
+    fn difficulty(&self) -> f32;
+
+    /// Looks for the first place in the puzzle where the technique eliminates or places something
+    fn apply(&self, sudoku: &Sudoku) -> Option<Step>;
+}
+
+/// The techniques solving tries, in order: the first one that finds a step gets it applied,
+/// after which solving starts again at the first technique
+pub struct Strategy {
+    techniques: Vec<Box<dyn Technique>>,
+}
+
+impl Strategy {
+    /// Without any techniques, solving only places the values that are the last option of
+    /// their cell. Strategy::default has the built-in techniques.
+    pub fn empty() -> Self {
+        Strategy {
+            techniques: Vec::new(),
+        }
+    }
+
+    /// The built-in techniques, including the ones that assume the puzzle has a single
+    /// solution. The caller vouches for that.
+    pub fn assuming_unique() -> Self {
+        Strategy::from(BuiltIn::DEFAULT_ASSUMING_UNIQUE).then(BuiltIn::LAST_RESORT)
+    }
+
+    /// Adds the technique after the ones already there
+    pub fn with(mut self, technique: impl Technique + 'static) -> Self {
+        self.techniques.push(Box::new(technique));
+        self
+    }
+
+    /// Adds the techniques after the ones already there
+    pub fn then(self, techniques: &[BuiltIn]) -> Self {
+        techniques
+            .iter()
+            .fold(self, |strategy, technique| strategy.with(*technique))
+    }
+
+    /// Drops the built-in technique
+    pub fn without(mut self, technique: BuiltIn) -> Self {
+        self.techniques
+            .retain(|other| other.name() != technique.name());
+        self
+    }
+
+    pub fn techniques(&self) -> impl Iterator<Item = &dyn Technique> {
+        self.techniques.iter().map(|technique| technique.as_ref())
+    }
+
+    /// The step found by the first technique that finds one
+    pub fn find(&self, sudoku: &Sudoku) -> Option<Step> {
+        self.find_with_technique(sudoku).map(|(_, step)| step)
+    }
+
+    /// Like find, along with the technique that found the step
+    pub fn find_with_technique(&self, sudoku: &Sudoku) -> Option<(&dyn Technique, Step)> {
+        self.techniques.iter().find_map(|technique| {
+            technique
+                .apply(sudoku)
+                .map(|step| (technique.as_ref(), step))
+        })
+    }
+}
+
+/// The built-in techniques that are sound for any puzzle, simplest first, with the last resort
+/// after them
+impl Default for Strategy {
+    fn default() -> Self {
+        Strategy::from(BuiltIn::DEFAULT).then(BuiltIn::LAST_RESORT)
+    }
+}
+
+impl From<&[BuiltIn]> for Strategy {
+    fn from(techniques: &[BuiltIn]) -> Self {
+        Strategy::empty().then(techniques)
+    }
+}
+
+#[cfg(test)]
+use crate::{Candidate, Evidence, SudokuError};
+
+#[cfg(test)]
+#[test]
+fn custom_technique_runs_in_order() -> crate::SudokuResult {
+    /// Places a 1 in the first cell of an empty puzzle
+    struct FirstCellIsOne;
+
+    impl Technique for FirstCellIsOne {
+        fn name(&self) -> &'static str {
+            "First cell is one"
+        }
+
+        fn difficulty(&self) -> f32 {
+            1.0
+        }
+
+        fn apply(&self, sudoku: &Sudoku) -> Option<Step> {
+            sudoku.options(0).contains(1).then(|| Step {
+                technique: self.name(),
+                placements: vec![Candidate::new(0, 1)],
+                eliminations: Vec::new(),
+                evidence: Evidence::Custom {
+                    description: "1 goes first".to_string(),
+                },
+            })
+        }
+    }
+
+    let strategy = Strategy::default().with(FirstCellIsOne);
+    assert_eq!(
+        strategy
+            .techniques()
+            .last()
+            .map(|technique| technique.name()),
+        Some("First cell is one")
+    );
+    assert_eq!(
+        strategy.find(&Sudoku::new()).map(|step| step.technique),
+        Some("First cell is one")
+    );
+
+    let strategy = Strategy::empty().with(FirstCellIsOne);
+    let mut sudoku = Sudoku::new();
+    assert_eq!(sudoku.solve_with(&strategy)?, 1);
+    assert_eq!(sudoku.value(0), Some(1));
+
+    let strategy = Strategy::default().without(BuiltIn::HiddenSingle);
+    assert_eq!(
+        strategy.techniques().count(),
+        BuiltIn::DEFAULT.len() + BuiltIn::LAST_RESORT.len() - 1
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn technique_without_effect_stops_solving() -> crate::SudokuResult {
+    /// Keeps eliminating 1 from the first cell
+    struct AlwaysOne;
+
+    impl Technique for AlwaysOne {
+        fn name(&self) -> &'static str {
+            "Always one"
+        }
+
+        fn difficulty(&self) -> f32 {
+            1.0
+        }
+
+        fn apply(&self, _: &Sudoku) -> Option<Step> {
+            Some(Step {
+                technique: self.name(),
+                placements: Vec::new(),
+                eliminations: vec![Candidate::new(0, 1)],
+                evidence: Evidence::Custom {
+                    description: "1 never goes first".to_string(),
+                },
+            })
+        }
+    }
+
+    let mut sudoku = Sudoku::new();
+    sudoku.eliminate_option(0, 1)?;
+    assert_eq!(
+        sudoku.solve_with(&Strategy::empty().with(AlwaysOne)),
+        Err(SudokuError::StepWithoutEffect {
+            technique: "Always one"
+        })
+    );
+    Ok(())
+}
@@ -1,13 +1,14 @@
+use std::collections::VecDeque;
+
 use crate::{
-    cell::Cell, CellIndices, CellOptions, CellOrigins, Cells, SudokuError, SudokuResult,
-    GRID_COLUMNS, GRID_ROWS, GRID_SIZE,
+    cell::Cell, Candidate, Candidates, CellIndices, Cells, Contradiction, Group, GroupOptions,
+    Step, Strategy, SudokuError, SudokuResult, GRID_COLUMNS, GRID_ROWS, GRID_SIZE,
 };
 
-type CellFilter<'i> = Box<dyn Fn(&&Cell) -> bool + 'i>;
-
+#[derive(Clone)]
 pub struct Sudoku {
     cells: Cells,
-    cell_indices: CellIndices,
+    cell_indices: &'static CellIndices,
     pub todo_count: usize,
 }
 
@@ -25,170 +26,290 @@ impl Sudoku {
 
         Self {
             cells,
-            cell_indices: CellIndices::new(),
+            cell_indices: CellIndices::shared(),
             todo_count: GRID_SIZE,
         }
     }
 
     pub fn choose(&mut self, cell_index: usize, value: usize) -> SudokuResult<usize> {
-        if cell_index >= GRID_SIZE {
-            Err(SudokuError::InvalidCellIndex { cell_index })
-        } else if value < 1 || value > GRID_COLUMNS {
-            Err(SudokuError::InvalidCellIndex { cell_index })
+        self.choose_traced(cell_index, value, None)
+    }
+
+    /// Like choose, adding the values placed to the trace when there is one
+    fn choose_traced(
+        &mut self,
+        cell_index: usize,
+        value: usize,
+        trace: Option<&mut Vec<Candidate>>,
+    ) -> SudokuResult<usize> {
+        check_candidate(cell_index, value)?;
+
+        let cell = &self.cells[cell_index];
+        if cell.has_value(value) {
+            Ok(0)
         } else {
-            let cell = &self.cells[cell_index];
-            if cell.has_value(value) {
-                Ok(0)
+            if cell.can_choose(value) {
+                self.propagate(Pending::Place { cell_index, value }, trace)
             } else {
-                if cell.can_choose(value) {
-                    Ok(self.assign_cell_value(cell_index, value))
-                } else {
-                    Err(SudokuError::CannotChoose {
-                        cell: cell.clone(),
-                        value,
-                    })
-                }
+                Err(SudokuError::CannotChoose { cell: *cell, value })
             }
         }
     }
 
-    fn assign_cell_value(&mut self, cell_index: usize, value: usize) -> usize {
-        let cell = &self.cells[cell_index];
+    #[cfg(test)]
+    fn assign_cell_value(&mut self, cell_index: usize, value: usize) -> SudokuResult<usize> {
+        self.propagate(Pending::Place { cell_index, value }, None)
+    }
 
-        if cell.has_value(value) {
-            0
-        } else {
-            let mut counter = 1_usize;
-            self.todo_count -= 1;
+    /// Removes an option from a cell and places whatever single options that leaves
+    pub(crate) fn eliminate_option(
+        &mut self,
+        cell_index: usize,
+        value: usize,
+    ) -> SudokuResult<usize> {
+        check_candidate(cell_index, value)?;
+        self.propagate(Pending::Eliminate { cell_index, value }, None)
+    }
 
-            let (row_nr, column_nr, square_nr) = {
-                let mut_cell = &mut self.cells[cell_index];
-                mut_cell.choose(value);
-                (mut_cell.row, mut_cell.column, mut_cell.square_nr)
-            };
+    /// Works through the consequences of a placement or elimination: placing a value eliminates it
+    /// from the peers, and a cell left with a single option gets that value placed. Each cell is
+    /// only revisited when its options change. Returns the number of values placed, which also
+    /// go to the trace, in order, when there is one.
+    fn propagate(
+        &mut self,
+        start: Pending,
+        mut trace: Option<&mut Vec<Candidate>>,
+    ) -> SudokuResult<usize> {
+        let mut counter = 0_usize;
+        let mut pending = VecDeque::from([start]);
+
+        while let Some(next) = pending.pop_front() {
+            match next {
+                Pending::Place { cell_index, value } => {
+                    if self.cells[cell_index].has_value(value) {
+                        continue;
+                    }
+
+                    counter += 1;
+                    self.todo_count -= 1;
+                    if let Some(trace) = trace.as_deref_mut() {
+                        trace.push(Candidate::new(cell_index, value));
+                    }
+                    let dropped = self.cells[cell_index].options - value.into();
+                    self.cells[cell_index].choose(value);
+                    for dropped_value in dropped.iter() {
+                        self.verify_consistency(cell_index, dropped_value)?;
+                    }
+
+                    for peer_index in self.cell_indices.peers(cell_index) {
+                        self.eliminate(*peer_index, value, &mut pending)?;
+                    }
+                }
+                Pending::Eliminate { cell_index, value } => {
+                    self.eliminate(cell_index, value, &mut pending)?
+                }
+            }
+        }
+
+        Ok(counter)
+    }
 
-            self.remove_options(value, row_nr, column_nr, square_nr, cell_index);
-
-            let solutions: Vec<(usize, usize)> = self
-                .cells
-                .iter()
-                .enumerate()
-                .filter_map(|(cell_index, cell)| match cell.solution() {
-                    Some(value) => Some((cell_index, value)),
-                    _ => None,
-                })
-                .collect();
-            for (cell_index, cell_value) in solutions {
-                counter += self.assign_cell_value(cell_index, cell_value);
+    /// Eliminations are applied right away, so a pending placement never conflicts with its peers
+    fn eliminate(
+        &mut self,
+        cell_index: usize,
+        value: usize,
+        pending: &mut VecDeque<Pending>,
+    ) -> SudokuResult {
+        let cell = &mut self.cells[cell_index];
+
+        if cell.remove_option_if_available(value) {
+            match cell.solution() {
+                Some(solution) => pending.push_back(Pending::Place {
+                    cell_index,
+                    value: solution,
+                }),
+                None if cell.todo_count == 0 => {
+                    return Err(SudokuError::Contradiction(Contradiction::NoOptions {
+                        cell_index,
+                    }))
+                }
+                None => (),
             }
 
-            counter
+            self.verify_consistency(cell_index, value)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Fails when the value can no longer be placed in one of the groups of the cell
+    fn verify_consistency(&self, cell_index: usize, value: usize) -> SudokuResult {
+        match self
+            .cell_indices
+            .groups_of(cell_index)
+            .iter()
+            .find(|group| {
+                !self
+                    .cells_of(**group)
+                    .any(|cell| cell.has_value(value) || cell.can_choose(value))
+            }) {
+            Some(group) => Err(SudokuError::Contradiction(Contradiction::NoPlace {
+                group: *group,
+                value,
+            })),
+            None => Ok(()),
         }
     }
 
     pub fn solve(&mut self) -> SudokuResult<usize> {
+        self.solve_with(&Strategy::default())
+    }
+
+    /// Like solve, but also applies the techniques that are only sound when the puzzle has a
+    /// single solution. The caller vouches for that.
+    pub fn solve_assuming_unique(&mut self) -> SudokuResult<usize> {
+        self.solve_with(&Strategy::assuming_unique())
+    }
+
+    /// Applies the steps the strategy finds until it finds none
+    pub fn solve_with(&mut self, strategy: &Strategy) -> SudokuResult<usize> {
         let mut count: usize = 0;
-        while let Some(choice) = self.locate_single_options() {
-            count += self.choose(choice.cell_index, choice.cell_value)?;
+        while let Some(step) = strategy.find(self) {
+            count += self.apply_step(&step)?;
         }
         Ok(count)
     }
 
-    fn locate_single_options(&self) -> Option<Choose> {
-        match self.locate_single_options_by_row() {
-            None => match self.locate_single_options_by_column() {
-                None => self.locate_single_options_by_square(),
-                result => result,
-            },
-            result => result,
+    /// Places hidden singles until they get stuck, without building steps
+    pub(crate) fn solve_singles(&mut self) -> SudokuResult<usize> {
+        let mut count: usize = 0;
+        while let Some((_, choice)) = self.locate_single_options() {
+            count += self.choose(choice.cell_index, choice.value)?;
         }
+        Ok(count)
     }
 
-    fn remove_options(
+    /// Places and eliminates the candidates of the step, returning the number of values placed.
+    /// Fails for a step that changes nothing, which would have solving find it again and again.
+    pub fn apply_step(&mut self, step: &Step) -> SudokuResult<usize> {
+        self.apply_step_traced(step, None)
+    }
+
+    /// Like apply_step, adding the values placed to the trace when there is one: those of the
+    /// step, and the ones left as the last option of their cell
+    pub(crate) fn apply_step_traced(
         &mut self,
-        value: usize,
-        row_nr: usize,
-        column_nr: usize,
-        square_nr: usize,
-        except_cell_index: usize,
-    ) {
-        {
-            let row_indices = self.cell_indices.for_row(row_nr);
-
-            for row_cell in self.cells.iter_mut().filter(|cell: &&mut Cell| {
-                cell.index != except_cell_index && row_indices.contains(&cell.index)
-            }) {
-                row_cell.remove_option_if_available(value);
-            }
+        step: &Step,
+        mut trace: Option<&mut Vec<Candidate>>,
+    ) -> SudokuResult<usize> {
+        for candidate in step.placements.iter().chain(step.eliminations.iter()) {
+            check_candidate(candidate.cell_index, candidate.value)?;
         }
-
-        {
-            let column_indices = self.cell_indices.for_column(column_nr);
-
-            for column_cell in self.cells.iter_mut().filter(|cell: &&mut Cell| {
-                cell.index != except_cell_index && column_indices.contains(&cell.index)
-            }) {
-                column_cell.remove_option_if_available(value);
-            }
+        let changes = step
+            .placements
+            .iter()
+            .any(|placement| !self.cells[placement.cell_index].has_value(placement.value))
+            || step.eliminations.iter().any(|elimination| {
+                self.cells[elimination.cell_index].can_choose(elimination.value)
+            });
+        if !changes {
+            return Err(SudokuError::StepWithoutEffect {
+                technique: step.technique,
+            });
         }
 
-        {
-            let square_indices = self.cell_indices.for_square(square_nr);
-
-            for square_cell in self.cells.iter_mut().filter(|cell: &&mut Cell| {
-                cell.index != except_cell_index && square_indices.contains(&cell.index)
-            }) {
-                square_cell.remove_option_if_available(value);
-            }
+        let mut count: usize = 0;
+        for placement in step.placements.iter() {
+            count +=
+                self.choose_traced(placement.cell_index, placement.value, trace.as_deref_mut())?;
         }
+        for elimination in step.eliminations.iter() {
+            count += self.propagate(
+                Pending::Eliminate {
+                    cell_index: elimination.cell_index,
+                    value: elimination.value,
+                },
+                trace.as_deref_mut(),
+            )?;
+        }
+        Ok(count)
     }
 
-    fn locate_single_options_by<'i>(
-        &'i self,
-        length: usize,
-        cell_filter: fn(&'i Self, index: usize) -> CellFilter<'i>,
-    ) -> Option<Choose> {
-        (0..length).find_map(|row| {
-            let mut group_options = GroupOptions::new();
+    /// The cell without value that has the fewest options left
+    pub(crate) fn most_constrained_cell(&self) -> Option<&Cell> {
+        self.cells
+            .iter()
+            .filter(|cell| cell.has_options())
+            .min_by_key(|cell| cell.todo_count)
+    }
 
-            for cell in self.cells.iter().filter(cell_filter(&self, row)) {
-                if cell.has_options() {
-                    group_options.xor(&cell.options, cell.index)
-                }
-            }
+    /// A value with one place left in a group, looking at rows, then columns, then squares
+    pub(crate) fn locate_single_options(&self) -> Option<(Group, Candidate)> {
+        match self.locate_single_options_by_row() {
+            None => match self.locate_single_options_by_column() {
+                None => self.locate_single_options_by_square(),
+                result => result,
+            },
+            result => result,
+        }
+    }
 
-            group_options.single_option()
+    fn locate_single_options_by(
+        &self,
+        mut groups: impl Iterator<Item = Group>,
+    ) -> Option<(Group, Candidate)> {
+        groups.find_map(|group| {
+            GroupOptions::of(self, group)
+                .single_option()
+                .map(|candidate| (group, candidate))
         })
     }
 
-    fn locate_single_options_by_row(&self) -> Option<Choose> {
-        self.locate_single_options_by(GRID_ROWS, Sudoku::cells_of_row)
+    fn locate_single_options_by_row(&self) -> Option<(Group, Candidate)> {
+        self.locate_single_options_by((0..GRID_ROWS).map(Group::Row))
     }
 
-    fn locate_single_options_by_column(&self) -> Option<Choose> {
-        self.locate_single_options_by(GRID_COLUMNS, Sudoku::cells_of_column)
+    fn locate_single_options_by_column(&self) -> Option<(Group, Candidate)> {
+        self.locate_single_options_by((0..GRID_COLUMNS).map(Group::Column))
     }
 
-    fn locate_single_options_by_square(&self) -> Option<Choose> {
-        self.locate_single_options_by(GRID_COLUMNS, Sudoku::cells_of_square)
+    fn locate_single_options_by_square(&self) -> Option<(Group, Candidate)> {
+        self.locate_single_options_by((0..GRID_COLUMNS).map(Group::Square))
     }
 
-    fn cells_of_row<'i>(&'i self, row_nr: usize) -> CellFilter<'i> {
-        let row_indices: &'i [usize] = self.cell_indices.for_row(row_nr);
-
-        Box::new(move |cell: &&Cell| row_indices.contains(&cell.index))
+    /// The value of the cell, counting cells row by row from 0
+    ///
+    /// # Panics
+    ///
+    /// When the cell index is 81 or more
+    pub fn value(&self, cell_index: usize) -> Option<usize> {
+        self.cells[cell_index].value
     }
 
-    fn cells_of_column<'i>(&'i self, column_nr: usize) -> CellFilter<'i> {
-        let column_indices: &'i [usize] = self.cell_indices.for_column(column_nr);
+    /// The values the cell can still get; none once it has a value
+    ///
+    /// # Panics
+    ///
+    /// When the cell index is 81 or more
+    pub fn options(&self, cell_index: usize) -> Candidates {
+        self.cells[cell_index].options
+    }
 
-        Box::new(move |cell: &&Cell| column_indices.contains(&cell.index))
+    pub(crate) fn cell(&self, cell_index: usize) -> &Cell {
+        &self.cells[cell_index]
     }
 
-    fn cells_of_square<'i>(&'i self, square_nr: usize) -> CellFilter<'i> {
-        let square_indices: &'i [usize] = self.cell_indices.for_square(square_nr);
+    /// The cells without value
+    pub(crate) fn open_cells(&self) -> impl Iterator<Item = &Cell> {
+        self.cells.iter().filter(|cell| cell.has_options())
+    }
 
-        Box::new(move |cell: &&Cell| square_indices.contains(&cell.index))
+    pub(crate) fn cells_of(&self, group: Group) -> impl Iterator<Item = &Cell> {
+        self.cell_indices
+            .for_group(group)
+            .iter()
+            .map(|cell_index| &self.cells[*cell_index])
     }
     /*
     #[cfg(test)]
@@ -218,6 +339,12 @@ impl Sudoku {
     }
 }
 
+impl Default for Sudoku {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl std::fmt::Display for Sudoku {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut rows = Vec::<String>::new();
@@ -246,89 +373,39 @@ impl std::fmt::Display for Sudoku {
     }
 }
 
-#[derive(Debug)]
-struct GroupOptions {
-    options: CellOptions,
-    set_from: CellOrigins,
-}
-
-impl Default for GroupOptions {
-    fn default() -> Self {
-        Self {
-            options: Default::default(),
-            set_from: Default::default(),
-        }
-    }
+enum Pending {
+    Place { cell_index: usize, value: usize },
+    Eliminate { cell_index: usize, value: usize },
 }
 
-impl GroupOptions {
-    fn new() -> Self {
-        GroupOptions::default()
-    }
-
-    fn xor(&mut self, other: &CellOptions, cell_index: usize) {
-        other
-            .iter()
-            .zip(self.set_from.iter_mut().zip(self.options.iter_mut()))
-            .filter(|(other, _)| **other)
-            .for_each(|(_, (used, option))| match *used {
-                Some(_) => *option = false,
-                None => {
-                    *option = true;
-                    *used = Some(cell_index)
-                }
-            });
-    }
-
-    fn single_option(&self) -> Option<Choose> {
-        self.options
-            .iter()
-            .enumerate()
-            .zip(self.set_from.iter())
-            .find_map(|((cell_value, is_single), cell_index)| {
-                if *is_single {
-                    Some(Choose {
-                        cell_index: cell_index.unwrap(),
-                        cell_value,
-                    })
-                } else {
-                    None
-                }
-            })
+/// Fails for a cell or value outside the grid
+fn check_candidate(cell_index: usize, value: usize) -> SudokuResult {
+    if cell_index >= GRID_SIZE {
+        Err(SudokuError::InvalidCellIndex { cell_index })
+    } else if !(1..=GRID_COLUMNS).contains(&value) {
+        Err(SudokuError::IllegalValue { value })
+    } else {
+        Ok(())
     }
 }
 
-struct Choose {
-    cell_index: usize,
-    cell_value: usize,
-}
-
 #[cfg(test)]
 #[test]
 fn pick_one() {
     let mut sudoku = Sudoku::new();
 
-    sudoku.assign_cell_value(10, 5);
-    sudoku.assign_cell_value(20, 6);
-    sudoku.assign_cell_value(18, 8);
-    sudoku.assign_cell_value(0, 4);
-    sudoku.assign_cell_value(2, 2);
+    sudoku.assign_cell_value(10, 5).unwrap();
+    sudoku.assign_cell_value(20, 6).unwrap();
+    sudoku.assign_cell_value(18, 8).unwrap();
+    sudoku.assign_cell_value(0, 4).unwrap();
+    sudoku.assign_cell_value(2, 2).unwrap();
 
     let s = sudoku
-        .cells
-        .iter()
-        .filter(sudoku.cells_of_square(0))
+        .cells_of(Group::Square(0))
         .map(|c| {
             let cont = match c.value {
                 Some(v) => format!("={}=", v),
-                None => format!(
-                    "{:?}",
-                    c.options
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(i, o)| if *o { Some(i) } else { None })
-                        .collect::<Vec<usize>>()
-                ),
+                None => format!("{:?}", c.options.iter().collect::<Vec<usize>>()),
             };
             format!("{:2}:[{}]", c.index, cont)
         })
@@ -337,3 +414,125 @@ fn pick_one() {
 
     assert_eq!(s, " 0:[=4=],  1:[[1, 3, 7, 9]],  2:[=2=],  9:[[1, 3, 7, 9]], 10:[=5=], 11:[[1, 3, 7, 9]], 18:[=8=], 19:[[1, 3, 7, 9]], 20:[=6=]". to_string())
 }
+
+#[cfg(test)]
+#[test]
+fn cell_without_options() -> SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    // r1c1 and r1c2 keep only 1 and 2, so 1 at r2c1 leaves 2 for both, while 1 still has a
+    // place in their row, columns and square
+    for (cell_index, value) in [(2, 5), (5, 6), (6, 7), (7, 8), (8, 9), (11, 3), (20, 4)] {
+        sudoku.choose(cell_index, value)?;
+    }
+
+    assert_eq!(
+        sudoku.choose(9, 1),
+        Err(SudokuError::Contradiction(Contradiction::NoOptions {
+            cell_index: 1
+        }))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn value_without_place() -> SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    sudoku.choose(9, 9)?;
+    sudoku.choose(21, 9)?;
+    sudoku.choose(6, 1)?;
+    sudoku.choose(7, 2)?;
+
+    assert_eq!(
+        sudoku.choose(35, 9),
+        Err(SudokuError::Contradiction(Contradiction::NoPlace {
+            group: Group::Row(0),
+            value: 9
+        }))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn choose_drops_last_place() -> SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    for cell_index in 1..GRID_COLUMNS {
+        sudoku.eliminate_option(cell_index, 1)?;
+    }
+
+    assert_eq!(
+        sudoku.choose(0, 2),
+        Err(SudokuError::Contradiction(Contradiction::NoPlace {
+            group: Group::Row(0),
+            value: 1
+        }))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn eliminate_places_single_options() -> SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    for cell_index in 0..7 {
+        sudoku.choose(cell_index, cell_index + 1)?;
+    }
+
+    assert_eq!(sudoku.eliminate_option(7, 9)?, 2);
+    assert_eq!(sudoku.cells[7].value, Some(8));
+    assert_eq!(sudoku.cells[8].value, Some(9));
+    assert_eq!(sudoku.todo_count, GRID_SIZE - 9);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn apply_step_checks_candidates() {
+    let mut sudoku = Sudoku::new();
+    let step = |eliminations| Step {
+        technique: "Custom",
+        placements: vec![Candidate::new(0, 1)],
+        eliminations,
+        evidence: crate::Evidence::Custom {
+            description: String::new(),
+        },
+    };
+
+    assert_eq!(
+        sudoku.apply_step(&step(vec![Candidate::new(100, 2)])),
+        Err(SudokuError::InvalidCellIndex { cell_index: 100 })
+    );
+    assert_eq!(
+        sudoku.apply_step(&step(vec![Candidate::new(1, 20)])),
+        Err(SudokuError::IllegalValue { value: 20 })
+    );
+    assert_eq!(sudoku.todo_count, GRID_SIZE);
+}
+
+#[cfg(test)]
+#[test]
+fn apply_step_without_effect() -> SudokuResult {
+    let mut sudoku = Sudoku::new();
+    sudoku.eliminate_option(0, 1)?;
+    let step = Step {
+        technique: "Custom",
+        placements: Vec::new(),
+        eliminations: vec![Candidate::new(0, 1)],
+        evidence: crate::Evidence::Custom {
+            description: String::new(),
+        },
+    };
+
+    assert_eq!(
+        sudoku.apply_step(&step),
+        Err(SudokuError::StepWithoutEffect {
+            technique: "Custom"
+        })
+    );
+    Ok(())
+}
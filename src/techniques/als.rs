@@ -0,0 +1,339 @@
+use std::collections::HashSet;
+
+use crate::{
+    indices, techniques::combinations, AlmostLockedSet, Candidate, Candidates, CellIndices,
+    CellOrigins, Evidence, Group, Step, Sudoku,
+};
+
+/// N cells in a group with N + 1 options between them: losing any one option locks the others
+struct Als {
+    group: Group,
+    cells: u128,
+    values: Candidates,
+    cells_with: CellOrigins,
+}
+
+impl Als {
+    fn overlaps(&self, other: &Als) -> bool {
+        self.cells & other.cells != 0
+    }
+
+    /// The values both sets have, where every cell with the value in one set sees every cell
+    /// with it in the other: at most one of the sets gets it
+    fn restricted_commons(&self, other: &Als) -> Candidates {
+        if self.overlaps(other) {
+            return Candidates::NONE;
+        }
+
+        (self.values & other.values)
+            .iter()
+            .filter(|value| {
+                seeing_all(self.cells_with[*value]) & other.cells_with[*value]
+                    == other.cells_with[*value]
+            })
+            .collect()
+    }
+
+    fn evidence(&self) -> AlmostLockedSet {
+        AlmostLockedSet {
+            group: self.group,
+            cells: indices(self.cells).collect(),
+            values: self.values,
+        }
+    }
+}
+
+/// Two sets with a restricted common value x: one of them is locked without x, so a value z
+/// both have ends up in one of them. Cells seeing every z of both sets lose it.
+pub(crate) fn als_xz(sudoku: &Sudoku) -> Option<Step> {
+    let sets = almost_locked_sets(sudoku);
+
+    sets.iter().enumerate().find_map(|(nr, first)| {
+        sets[nr + 1..].iter().find_map(|second| {
+            first.restricted_commons(second).iter().find_map(|common| {
+                let eliminations = eliminations(
+                    sudoku,
+                    &[first, second],
+                    (first.values & second.values) - common.into(),
+                );
+                step("ALS-XZ", eliminations, &[first, second], vec![common], None)
+            })
+        })
+    })
+}
+
+/// A pivot set with different restricted common values x and y with two other sets: one of
+/// those gets locked, so a value z both have ends up in one of them
+pub(crate) fn als_xy_wing(sudoku: &Sudoku) -> Option<Step> {
+    let sets = almost_locked_sets(sudoku);
+
+    sets.iter().find_map(|pivot| {
+        let linked: Vec<(&Als, usize)> = sets
+            .iter()
+            .flat_map(|set| {
+                pivot
+                    .restricted_commons(set)
+                    .iter()
+                    .map(move |common| (set, common))
+            })
+            .collect();
+
+        linked.iter().enumerate().find_map(|(nr, (first, x))| {
+            linked[nr + 1..].iter().find_map(|(second, y)| {
+                if x == y || first.overlaps(second) {
+                    return None;
+                }
+
+                let values = (first.values & second.values) - Candidates::from(*x) - (*y).into();
+                step(
+                    "ALS-XY-Wing",
+                    eliminations(sudoku, &[first, second], values),
+                    &[first, pivot, second],
+                    vec![*x, *y],
+                    None,
+                )
+            })
+        })
+    })
+}
+
+/// A stem cell whose every option v is linked to a set, in which all cells with v see the stem.
+/// Whatever the stem gets, one of the sets gets locked, so a value z all sets have ends up in
+/// one of them.
+pub(crate) fn death_blossom(sudoku: &Sudoku) -> Option<Step> {
+    let sets = almost_locked_sets(sudoku);
+    let cell_indices = CellIndices::shared();
+
+    sudoku
+        .open_cells()
+        .filter(|stem| stem.todo_count <= 3)
+        .find_map(|stem| {
+            let petals: Vec<(usize, Vec<&Als>)> = stem
+                .available_options()
+                .map(|value| {
+                    let petals = sets
+                        .iter()
+                        .filter(|set| {
+                            set.cells & (1 << stem.index) == 0
+                                && set.values.contains(value)
+                                && set.cells_with[value] & !cell_indices.peer_mask(stem.index) == 0
+                        })
+                        .collect();
+                    (value, petals)
+                })
+                .collect();
+
+            blossom(sudoku, stem.index, stem.options, &petals, &mut Vec::new())
+        })
+}
+
+/// Picks a petal per option of the stem, as long as they don't overlap and share a value
+fn blossom<'s>(
+    sudoku: &Sudoku,
+    stem: usize,
+    stem_values: Candidates,
+    petals: &[(usize, Vec<&'s Als>)],
+    chosen: &mut Vec<&'s Als>,
+) -> Option<Step> {
+    let values = chosen
+        .iter()
+        .fold(!stem_values, |values, set| values & set.values);
+
+    if chosen.len() == petals.len() {
+        return step(
+            "Death Blossom",
+            eliminations(sudoku, chosen, values),
+            chosen,
+            petals.iter().map(|(value, _)| *value).collect(),
+            Some(stem),
+        );
+    }
+
+    petals[chosen.len()].1.iter().find_map(|petal| {
+        if (values & petal.values).is_empty() || chosen.iter().any(|set| set.overlaps(petal)) {
+            return None;
+        }
+
+        chosen.push(petal);
+        let step = blossom(sudoku, stem, stem_values, petals, chosen);
+        chosen.pop();
+        step
+    })
+}
+
+/// The sets in all groups, each set of cells once
+fn almost_locked_sets(sudoku: &Sudoku) -> Vec<Als> {
+    let mut seen: HashSet<u128> = HashSet::new();
+    let mut sets = Vec::new();
+
+    for group in Group::all() {
+        let open_cells: Vec<usize> = sudoku
+            .cells_of(group)
+            .filter(|cell| cell.has_options())
+            .map(|cell| cell.index)
+            .collect();
+
+        for size in 1..open_cells.len() {
+            for cells in combinations(&open_cells, size) {
+                let values = cells.iter().fold(Candidates::NONE, |values, cell_index| {
+                    values | sudoku.cell(*cell_index).options
+                });
+                let mask = cells
+                    .iter()
+                    .fold(0_u128, |mask, cell_index| mask | (1 << cell_index));
+
+                if values.len() == size + 1 && seen.insert(mask) {
+                    let mut cells_with: CellOrigins = Default::default();
+                    for cell_index in cells.iter() {
+                        for value in sudoku.cell(*cell_index).available_options() {
+                            cells_with[value] |= 1 << cell_index;
+                        }
+                    }
+
+                    sets.push(Als {
+                        group,
+                        cells: mask,
+                        values,
+                        cells_with,
+                    });
+                }
+            }
+        }
+    }
+
+    sets
+}
+
+/// The cells that see all cells of the mask
+fn seeing_all(cells: u128) -> u128 {
+    let cell_indices = CellIndices::shared();
+    indices(cells).fold(!0, |seeing, cell_index| {
+        seeing & cell_indices.peer_mask(cell_index)
+    })
+}
+
+/// Per value, the options of cells that see every cell with the value in all sets
+fn eliminations(sudoku: &Sudoku, sets: &[&Als], values: Candidates) -> Vec<Candidate> {
+    values
+        .iter()
+        .flat_map(|value| {
+            let cells = sets
+                .iter()
+                .fold(0_u128, |cells, set| cells | set.cells_with[value]);
+            indices(seeing_all(cells))
+                .filter(move |cell_index| sudoku.cell(*cell_index).can_choose(value))
+                .map(move |cell_index| Candidate::new(cell_index, value))
+        })
+        .collect()
+}
+
+fn step(
+    technique: &'static str,
+    eliminations: Vec<Candidate>,
+    sets: &[&Als],
+    restricted: Vec<usize>,
+    stem: Option<usize>,
+) -> Option<Step> {
+    if eliminations.is_empty() {
+        None
+    } else {
+        Some(Step {
+            technique,
+            placements: Vec::new(),
+            eliminations,
+            evidence: Evidence::AlmostLockedSets {
+                sets: sets.iter().map(|set| set.evidence()).collect(),
+                restricted,
+                stem,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+use crate::techniques::with_options;
+
+#[cfg(test)]
+#[test]
+fn als_xz_with_two_sets() -> crate::SudokuResult {
+    // r1c1 {1,2}, and r1c5 {1,3} with r2c5 {2,3}: 1 is restricted, so 2 is in one of them
+    let sudoku = with_options(&[(0, &[1, 2]), (4, &[1, 3]), (13, &[2, 3])])?;
+
+    let step = als_xz(&sudoku).expect("an ALS-XZ");
+    assert_eq!(
+        step.evidence,
+        Evidence::AlmostLockedSets {
+            sets: vec![
+                AlmostLockedSet {
+                    group: Group::Row(0),
+                    cells: vec![0],
+                    values: [1, 2].into_iter().collect(),
+                },
+                AlmostLockedSet {
+                    group: Group::Column(4),
+                    cells: vec![4, 13],
+                    values: [1, 2, 3].into_iter().collect(),
+                },
+            ],
+            restricted: vec![1],
+            stem: None,
+        }
+    );
+    assert_eq!(
+        step.eliminations,
+        [3, 5, 9, 10, 11].map(|cell_index| Candidate::new(cell_index, 2))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn als_xy_wing_around_pivot() -> crate::SudokuResult {
+    // pivot r5c5 {1,2}, linked by 1 to r5c1 with r6c2 {1,3,4} and by 2 to r1c5 {2,3}
+    let sudoku = with_options(&[(40, &[1, 2]), (36, &[1, 3]), (46, &[3, 4]), (4, &[2, 3])])?;
+
+    let step = als_xy_wing(&sudoku).expect("an ALS-XY-Wing");
+    let Evidence::AlmostLockedSets {
+        sets,
+        restricted,
+        stem,
+    } = step.evidence
+    else {
+        panic!("almost locked sets")
+    };
+    assert_eq!(sets.len(), 3);
+    assert_eq!(restricted.len(), 2);
+    assert_eq!(stem, None);
+    assert_eq!(step.eliminations, vec![Candidate::new(0, 3)]);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn death_blossom_with_two_petals() -> crate::SudokuResult {
+    // stem r5c5 {1,2}, petals r5c1 {1,3} and r1c5 {2,3}
+    let sudoku = with_options(&[(40, &[1, 2]), (36, &[1, 3]), (4, &[2, 3])])?;
+
+    let step = death_blossom(&sudoku).expect("a death blossom");
+    assert_eq!(
+        step.evidence,
+        Evidence::AlmostLockedSets {
+            sets: vec![
+                AlmostLockedSet {
+                    group: Group::Row(4),
+                    cells: vec![36],
+                    values: [1, 3].into_iter().collect(),
+                },
+                AlmostLockedSet {
+                    group: Group::Row(0),
+                    cells: vec![4],
+                    values: [2, 3].into_iter().collect(),
+                },
+            ],
+            restricted: vec![1, 2],
+            stem: Some(40),
+        }
+    );
+    assert_eq!(step.eliminations, vec![Candidate::new(0, 3)]);
+    Ok(())
+}
@@ -0,0 +1,277 @@
+use std::collections::VecDeque;
+
+use crate::{
+    techniques::conjugate_pairs, Candidate, CellIndices, Evidence, Link, Step, Sudoku,
+    GRID_COLUMNS, GRID_SIZE,
+};
+
+const NODE_COUNT: usize = GRID_SIZE * GRID_COLUMNS;
+
+/// Which links a chain may use
+#[derive(Clone, Copy)]
+pub(crate) enum Links {
+    /// Strong links between the two places for a value in a group, weak links between peers
+    SingleValue,
+    /// Strong links between the two options of a cell, weak links between peers
+    Bivalue,
+    /// Both kinds of strong links, weak links between peers and within cells
+    All,
+}
+
+impl Links {
+    fn name(self) -> &'static str {
+        match self {
+            Links::SingleValue => "X-Chain",
+            Links::Bivalue => "XY-Chain",
+            Links::All => "AIC",
+        }
+    }
+}
+
+/// The candidates of the puzzle as nodes of a graph: two candidates are linked strongly when at
+/// least one of them is true, and weakly when at most one is
+struct Inferences {
+    strong: Vec<Vec<usize>>,
+    weak: Vec<Vec<usize>>,
+}
+
+impl Inferences {
+    fn of(sudoku: &Sudoku, links: Links) -> Self {
+        let mut strong = vec![Vec::new(); NODE_COUNT];
+        let mut weak = vec![Vec::new(); NODE_COUNT];
+        let cell_indices = CellIndices::shared();
+
+        for cell in sudoku.open_cells() {
+            for value in cell.available_options() {
+                let from = node(Candidate::new(cell.index, value));
+
+                for peer_index in cell_indices.peers(cell.index) {
+                    if sudoku.cell(*peer_index).can_choose(value) {
+                        weak[from].push(node(Candidate::new(*peer_index, value)));
+                    }
+                }
+                for other in cell.available_options().filter(|other| *other != value) {
+                    let to = node(Candidate::new(cell.index, other));
+                    if matches!(links, Links::All) {
+                        weak[from].push(to);
+                    }
+                    if cell.todo_count == 2 && !matches!(links, Links::SingleValue) {
+                        strong[from].push(to);
+                    }
+                }
+            }
+        }
+
+        if !matches!(links, Links::Bivalue) {
+            for value in 1..=GRID_COLUMNS {
+                for (_, one, other) in conjugate_pairs(sudoku, value) {
+                    let (one, other) = (
+                        node(Candidate::new(one, value)),
+                        node(Candidate::new(other, value)),
+                    );
+                    if !strong[one].contains(&other) {
+                        strong[one].push(other);
+                        strong[other].push(one);
+                    }
+                }
+            }
+        }
+
+        Self { strong, weak }
+    }
+
+    /// Walks alternating links breadth first, starting with a strong link: when the start is
+    /// false, every node reached through a strong link is true. The first chain of at least
+    /// three links that eliminates something becomes the step.
+    fn chain_from(&self, sudoku: &Sudoku, links: Links, start: usize) -> Option<Step> {
+        // per node and whether it was reached through a strong link, the state it came from
+        let mut reached_from: Vec<Option<usize>> = vec![None; NODE_COUNT * 2];
+        let start_state = start * 2;
+        let mut pending = VecDeque::from([(start_state, 0_usize)]);
+        reached_from[start_state] = Some(start_state);
+
+        while let Some((state, length)) = pending.pop_front() {
+            let (current, through_strong) = (state / 2, state % 2 == 1);
+
+            if through_strong && length >= 3 {
+                if let Some(step) = self.step(sudoku, links, start, current, &reached_from, state) {
+                    return Some(step);
+                }
+            }
+
+            let next_links = if through_strong {
+                &self.weak[current]
+            } else {
+                &self.strong[current]
+            };
+            for next in next_links {
+                let next_state = next * 2 + usize::from(!through_strong);
+                if reached_from[next_state].is_none() {
+                    reached_from[next_state] = Some(state);
+                    pending.push_back((next_state, length + 1));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Either the start or the end is true, so everything weakly linked to both is false. This
+    /// covers discontinuous loops too: a loop with two weak links at a candidate is a chain
+    /// whose ends both see it, and one with two strong links at a candidate eliminates the
+    /// candidate before it in the loop.
+    fn step(
+        &self,
+        sudoku: &Sudoku,
+        links: Links,
+        start: usize,
+        end: usize,
+        reached_from: &[Option<usize>],
+        end_state: usize,
+    ) -> Option<Step> {
+        let eliminations = weakly_linked_to_both(sudoku, candidate(start), candidate(end));
+        if eliminations.is_empty() {
+            return None;
+        }
+
+        let mut chain = Vec::new();
+        let mut state = end_state;
+        while let Some(previous) = reached_from[state].filter(|previous| *previous != state) {
+            chain.push(Link::new(
+                candidate(previous / 2),
+                candidate(state / 2),
+                state % 2 == 1,
+            ));
+            state = previous;
+        }
+        chain.reverse();
+
+        Some(Step {
+            technique: links.name(),
+            placements: Vec::new(),
+            eliminations,
+            evidence: Evidence::Chain { links: chain },
+        })
+    }
+}
+
+/// Finds alternating inference chains that use the given kinds of links
+pub(crate) fn chain(sudoku: &Sudoku, links: Links) -> Option<Step> {
+    let inferences = Inferences::of(sudoku, links);
+
+    (0..NODE_COUNT)
+        .filter(|start| !inferences.strong[*start].is_empty())
+        .find_map(|start| inferences.chain_from(sudoku, links, start))
+}
+
+/// Candidates in the same cell with another value, or with the same value in a peer
+fn weakly_linked(one: Candidate, other: Candidate, sudoku: &Sudoku) -> bool {
+    if one.cell_index == other.cell_index {
+        one.value != other.value
+    } else {
+        one.value == other.value
+            && sudoku
+                .cell(one.cell_index)
+                .sees(sudoku.cell(other.cell_index))
+    }
+}
+
+fn weakly_linked_to_both(sudoku: &Sudoku, start: Candidate, end: Candidate) -> Vec<Candidate> {
+    sudoku
+        .open_cells()
+        .flat_map(|cell| {
+            cell.available_options()
+                .map(move |value| Candidate::new(cell.index, value))
+        })
+        .filter(|candidate| {
+            weakly_linked(*candidate, start, sudoku) && weakly_linked(*candidate, end, sudoku)
+        })
+        .collect()
+}
+
+fn node(candidate: Candidate) -> usize {
+    candidate.cell_index * GRID_COLUMNS + candidate.value - 1
+}
+
+fn candidate(node: usize) -> Candidate {
+    Candidate::new(node / GRID_COLUMNS, node % GRID_COLUMNS + 1)
+}
+
+#[cfg(test)]
+#[test]
+fn x_chain_on_one_value() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+    keep_only(&mut sudoku, Group::Row(0), 3, &[0, 5])?;
+    keep_only(&mut sudoku, Group::Square(1), 3, &[5, 21])?;
+    keep_only(&mut sudoku, Group::Column(3), 3, &[21, 66])?;
+
+    let step = chain(&sudoku, Links::SingleValue).expect("an X-Chain");
+    assert_eq!(step.technique, "X-Chain");
+    assert_eq!(
+        step.evidence,
+        Evidence::Chain {
+            links: vec![
+                Link::new(Candidate::new(0, 3), Candidate::new(5, 3), true),
+                Link::new(Candidate::new(5, 3), Candidate::new(21, 3), false),
+                Link::new(Candidate::new(21, 3), Candidate::new(66, 3), true),
+            ]
+        }
+    );
+    assert_eq!(step.eliminations, vec![Candidate::new(63, 3)]);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn xy_chain_through_bivalue_cells() -> crate::SudokuResult {
+    // r1c1 {1,2}, r1c5 {2,3}, r5c5 {3,4} and r5c9 {4,1}: r1c1 or r5c9 is 1
+    let sudoku = with_options(&[(0, &[1, 2]), (4, &[2, 3]), (40, &[3, 4]), (44, &[1, 4])])?;
+
+    let step = chain(&sudoku, Links::Bivalue).expect("an XY-Chain");
+    assert_eq!(step.technique, "XY-Chain");
+    let Evidence::Chain { links } = step.evidence else {
+        panic!("a chain")
+    };
+    assert_eq!(links.len(), 7);
+    assert_eq!(links[0].from, Candidate::new(0, 1));
+    assert_eq!(links[6].to, Candidate::new(44, 1));
+    assert_eq!(
+        step.eliminations,
+        vec![Candidate::new(8, 1), Candidate::new(36, 1)]
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn aic_mixes_links() -> crate::SudokuResult {
+    // 5 in row 1 at r1c1 or r1c7, 7 in the top right square at r1c7 or r1c9, r1c1 {5,7}
+    let mut sudoku = with_options(&[(0, &[5, 7])])?;
+    keep_only(&mut sudoku, Group::Row(0), 5, &[0, 6])?;
+    keep_only(&mut sudoku, Group::Square(2), 7, &[6, 8])?;
+
+    assert_eq!(chain(&sudoku, Links::SingleValue), None);
+    assert_eq!(chain(&sudoku, Links::Bivalue), None);
+
+    let step = chain(&sudoku, Links::All).expect("an AIC");
+    assert_eq!(step.technique, "AIC");
+    assert_eq!(
+        step.evidence,
+        Evidence::Chain {
+            links: vec![
+                Link::new(Candidate::new(0, 5), Candidate::new(0, 7), true),
+                Link::new(Candidate::new(0, 7), Candidate::new(6, 7), false),
+                Link::new(Candidate::new(6, 7), Candidate::new(8, 7), true),
+            ]
+        }
+    );
+    // r1c1 is 5 or r1c9 is 7, either way r1c1 isn't 7
+    assert_eq!(step.eliminations, vec![Candidate::new(0, 7)]);
+    Ok(())
+}
+
+#[cfg(test)]
+use crate::{
+    techniques::{keep_only, with_options},
+    Group,
+};
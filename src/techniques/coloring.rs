@@ -0,0 +1,266 @@
+use crate::{
+    indices, techniques::conjugate_pairs, Candidate, Evidence, Step, Sudoku, GRID_COLUMNS,
+    GRID_SIZE,
+};
+
+#[cfg(test)]
+use crate::{techniques::keep_only, Group};
+
+/// Per cell, bit n is set when the value is an option for only the cell and cell n in a group
+struct StrongLinks([u128; GRID_SIZE]);
+
+impl StrongLinks {
+    fn of(sudoku: &Sudoku, value: usize) -> Self {
+        let mut links = [0_u128; GRID_SIZE];
+        for (_, one, other) in conjugate_pairs(sudoku, value) {
+            links[one] |= 1 << other;
+            links[other] |= 1 << one;
+        }
+        Self(links)
+    }
+
+    /// Colors the cells reachable through strong links alternately, starting from each cell
+    /// that has a link and isn't colored yet
+    fn clusters(&self) -> Vec<Cluster> {
+        let mut colored = 0_u128;
+        let mut clusters = Vec::new();
+
+        for start in 0..GRID_SIZE {
+            if self.0[start] == 0 || colored & (1 << start) != 0 {
+                continue;
+            }
+
+            let mut colors = [1_u128 << start, 0];
+            let mut pending = vec![(start, 0)];
+            while let Some((cell_index, color)) = pending.pop() {
+                for linked in indices(self.0[cell_index]) {
+                    if (colors[0] | colors[1]) & (1 << linked) == 0 {
+                        colors[1 - color] |= 1 << linked;
+                        pending.push((linked, 1 - color));
+                    }
+                }
+            }
+
+            colored |= colors[0] | colors[1];
+            clusters.push(Cluster {
+                colors: colors.map(|color| indices(color).collect()),
+            });
+        }
+
+        clusters
+    }
+}
+
+/// Cells connected by strong links on a value: either all cells of the first color get the
+/// value, or all cells of the second one
+struct Cluster {
+    colors: [Vec<usize>; 2],
+}
+
+impl Cluster {
+    fn contains(&self, cell_index: usize) -> bool {
+        self.colors.iter().any(|color| color.contains(&cell_index))
+    }
+
+    /// A color with two cells that see each other can't have the value
+    fn wrapped_color(&self, sudoku: &Sudoku) -> Option<usize> {
+        (0..2).find(|color| sees_any(sudoku, &self.colors[*color], &self.colors[*color]))
+    }
+
+    fn evidence(&self, value: usize, others: &[&Cluster]) -> Evidence {
+        Evidence::Coloring {
+            value,
+            clusters: std::iter::once(self)
+                .chain(others.iter().copied())
+                .map(|cluster| cluster.colors.clone())
+                .collect(),
+        }
+    }
+}
+
+/// Colors the strong links of a value. When two cells of one color see each other, that color
+/// loses the value (color wrap). Otherwise, cells that see both colors lose it (color trap).
+pub(crate) fn simple_coloring(sudoku: &Sudoku) -> Option<Step> {
+    (1..=GRID_COLUMNS).find_map(|value| {
+        StrongLinks::of(sudoku, value)
+            .clusters()
+            .iter()
+            .find_map(|cluster| match cluster.wrapped_color(sudoku) {
+                Some(color) => Some(Step {
+                    technique: "Simple coloring",
+                    placements: Vec::new(),
+                    eliminations: candidates(&cluster.colors[color], value),
+                    evidence: cluster.evidence(value, &[]),
+                }),
+                None => {
+                    let eliminations = seeing_both(sudoku, value, &[cluster], &cluster.colors);
+                    if eliminations.is_empty() {
+                        None
+                    } else {
+                        Some(Step {
+                            technique: "Simple coloring",
+                            placements: Vec::new(),
+                            eliminations,
+                            evidence: cluster.evidence(value, &[]),
+                        })
+                    }
+                }
+            })
+    })
+}
+
+/// Two clusters of the same value, with a cell of one color in the first seeing a cell of one
+/// color in the second: at most one of those colors has the value, so at least one of the
+/// opposite colors has. Cells that see both opposite colors lose the value, and a color that
+/// sees both colors of the other cluster loses it everywhere.
+pub(crate) fn multi_coloring(sudoku: &Sudoku) -> Option<Step> {
+    (1..=GRID_COLUMNS).find_map(|value| {
+        let clusters = StrongLinks::of(sudoku, value).clusters();
+
+        clusters.iter().enumerate().find_map(|(nr, first)| {
+            clusters.iter().skip(nr + 1).find_map(|second| {
+                multi_coloring_with(sudoku, value, first, second)
+                    .or_else(|| multi_coloring_with(sudoku, value, second, first))
+            })
+        })
+    })
+}
+
+fn multi_coloring_with(
+    sudoku: &Sudoku,
+    value: usize,
+    first: &Cluster,
+    second: &Cluster,
+) -> Option<Step> {
+    let step = |eliminations: Vec<Candidate>| {
+        if eliminations.is_empty() {
+            None
+        } else {
+            Some(Step {
+                technique: "Multi-coloring",
+                placements: Vec::new(),
+                eliminations,
+                evidence: first.evidence(value, &[second]),
+            })
+        }
+    };
+
+    (0..2).find_map(|color| {
+        let sees =
+            [0, 1].map(|other| sees_any(sudoku, &first.colors[color], &second.colors[other]));
+
+        if sees[0] && sees[1] {
+            step(candidates(&first.colors[color], value))
+        } else {
+            (0..2).filter(|other| sees[*other]).find_map(|other| {
+                step(seeing_both(
+                    sudoku,
+                    value,
+                    &[first, second],
+                    &[
+                        first.colors[1 - color].clone(),
+                        second.colors[1 - other].clone(),
+                    ],
+                ))
+            })
+        }
+    })
+}
+
+fn sees_any(sudoku: &Sudoku, cells: &[usize], others: &[usize]) -> bool {
+    cells.iter().any(|cell_index| {
+        others
+            .iter()
+            .any(|other| sudoku.cell(*cell_index).sees(sudoku.cell(*other)))
+    })
+}
+
+/// The options for the value in cells outside the clusters that see both colors
+fn seeing_both(
+    sudoku: &Sudoku,
+    value: usize,
+    clusters: &[&Cluster],
+    colors: &[Vec<usize>; 2],
+) -> Vec<Candidate> {
+    sudoku
+        .open_cells()
+        .filter(|cell| {
+            cell.can_choose(value)
+                && !clusters.iter().any(|cluster| cluster.contains(cell.index))
+                && colors
+                    .iter()
+                    .all(|color| sees_any(sudoku, &[cell.index], color))
+        })
+        .map(|cell| Candidate::new(cell.index, value))
+        .collect()
+}
+
+fn candidates(cells: &[usize], value: usize) -> Vec<Candidate> {
+    cells
+        .iter()
+        .map(|cell_index| Candidate::new(*cell_index, value))
+        .collect()
+}
+
+#[cfg(test)]
+#[test]
+fn color_trap() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+    keep_only(&mut sudoku, Group::Row(0), 3, &[0, 5])?;
+    keep_only(&mut sudoku, Group::Square(1), 3, &[5, 21])?;
+    keep_only(&mut sudoku, Group::Column(3), 3, &[21, 66])?;
+
+    let step = simple_coloring(&sudoku).expect("a color trap");
+    assert_eq!(step.technique, "Simple coloring");
+    assert_eq!(
+        step.evidence,
+        Evidence::Coloring {
+            value: 3,
+            clusters: vec![[vec![0, 21], vec![5, 66]]],
+        }
+    );
+    // sees 0 in its column and 66 in its row
+    assert_eq!(step.eliminations, vec![Candidate::new(63, 3)]);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn color_wrap() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+    keep_only(&mut sudoku, Group::Row(0), 3, &[0, 5])?;
+    keep_only(&mut sudoku, Group::Square(1), 3, &[5, 21])?;
+    keep_only(&mut sudoku, Group::Column(3), 3, &[21, 66])?;
+    keep_only(&mut sudoku, Group::Row(7), 3, &[63, 66])?;
+
+    let step = simple_coloring(&sudoku).expect("a color wrap");
+    assert_eq!(step.technique, "Simple coloring");
+    // 0 and 63 share a column and a color
+    assert_eq!(
+        step.eliminations,
+        [0, 21, 63].map(|cell_index| Candidate::new(cell_index, 3))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn multi_coloring_two_clusters() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+    keep_only(&mut sudoku, Group::Row(0), 6, &[0, 5])?;
+    keep_only(&mut sudoku, Group::Column(1), 6, &[10, 64])?;
+
+    assert_eq!(simple_coloring(&sudoku), None);
+
+    // 0 sees 10, so 5 or 64 gets the value
+    let step = multi_coloring(&sudoku).expect("a multi-coloring");
+    assert_eq!(
+        step.evidence,
+        Evidence::Coloring {
+            value: 6,
+            clusters: vec![[vec![0], vec![5]], [vec![10], vec![64]]],
+        }
+    );
+    assert_eq!(step.eliminations, vec![Candidate::new(68, 6)]);
+    Ok(())
+}
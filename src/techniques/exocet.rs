@@ -0,0 +1,252 @@
+use crate::{
+    Candidate, Candidates, Evidence, Step, Sudoku, GRID_COLUMNS, GRID_ROWS, GRID_SQUARE_SIDE,
+};
+
+/// Looks along rows or along columns; the other orientation swaps rows and columns
+#[derive(Clone, Copy)]
+enum Orientation {
+    Rows,
+    Columns,
+}
+
+impl Orientation {
+    fn cell_index(self, row_nr: usize, column_nr: usize) -> usize {
+        match self {
+            Orientation::Rows => row_nr * GRID_COLUMNS + column_nr,
+            Orientation::Columns => column_nr * GRID_COLUMNS + row_nr,
+        }
+    }
+}
+
+/// Two base cells on a line in a square with three or four options between them, and a target
+/// cell in each of the other squares of the band, on the other two lines. When the companion
+/// cells, which share a square and a cross line with a target, lack the base values, and every
+/// base value can be covered by two houses on the cross lines outside the band, both values of
+/// the base cells end up in the targets. So the targets lose their other options, and the base
+/// cells lose the options that neither target has.
+pub(crate) fn junior_exocet(sudoku: &Sudoku) -> Option<Step> {
+    [Orientation::Rows, Orientation::Columns]
+        .into_iter()
+        .find_map(|orientation| {
+            (0..GRID_ROWS).find_map(|base_row| {
+                (0..GRID_SQUARE_SIDE).find_map(|stack| {
+                    let first = stack * GRID_SQUARE_SIDE;
+                    [(0, 1, 2), (0, 2, 1), (1, 2, 0)]
+                        .into_iter()
+                        .find_map(|(one, other, cross)| {
+                            Exocet {
+                                sudoku,
+                                orientation,
+                                base_row,
+                                stack,
+                                base_columns: [first + one, first + other],
+                                cross_column: first + cross,
+                            }
+                            .find()
+                        })
+                })
+            })
+        })
+}
+
+struct Exocet<'s> {
+    sudoku: &'s Sudoku,
+    orientation: Orientation,
+    base_row: usize,
+    stack: usize,
+    base_columns: [usize; 2],
+    cross_column: usize,
+}
+
+impl Exocet<'_> {
+    fn find(&self) -> Option<Step> {
+        let base = self
+            .base_columns
+            .map(|column_nr| self.orientation.cell_index(self.base_row, column_nr));
+        if !base
+            .iter()
+            .all(|cell_index| self.sudoku.cell(*cell_index).todo_count >= 2)
+        {
+            return None;
+        }
+
+        let values = self.options(&base);
+        if !(3..=4).contains(&values.len()) {
+            return None;
+        }
+
+        let band = self.base_row / GRID_SQUARE_SIDE * GRID_SQUARE_SIDE;
+        let rows: Vec<usize> = (band..band + GRID_SQUARE_SIDE)
+            .filter(|row_nr| *row_nr != self.base_row)
+            .collect();
+        let stacks: Vec<usize> = (0..GRID_SQUARE_SIDE)
+            .filter(|stack| *stack != self.stack)
+            .collect();
+
+        [(rows[0], rows[1]), (rows[1], rows[0])]
+            .into_iter()
+            .find_map(|(first_row, second_row)| {
+                columns_of(stacks[0]).find_map(|first_column| {
+                    columns_of(stacks[1]).find_map(|second_column| {
+                        self.step(
+                            &base,
+                            values,
+                            [(first_row, first_column), (second_row, second_column)],
+                        )
+                    })
+                })
+            })
+    }
+
+    fn step(
+        &self,
+        base: &[usize; 2],
+        values: Candidates,
+        targets: [(usize, usize); 2],
+    ) -> Option<Step> {
+        let [(first_row, first_column), (second_row, second_column)] = targets;
+        let cell_index = |(row_nr, column_nr)| self.orientation.cell_index(row_nr, column_nr);
+        let target_cells = targets.map(cell_index);
+        let companions = [(second_row, first_column), (first_row, second_column)].map(cell_index);
+
+        let targets_fit = target_cells.iter().all(|cell_index| {
+            let cell = self.sudoku.cell(*cell_index);
+            cell.has_options() && !(cell.options & values).is_empty()
+        });
+        let companions_free = companions.iter().all(|cell_index| {
+            let cell = self.sudoku.cell(*cell_index);
+            (cell.options & values).is_empty()
+                && !cell.value.is_some_and(|value| values.contains(value))
+        });
+        if !targets_fit
+            || !companions_free
+            || !values
+                .iter()
+                .all(|value| self.covered_by_two_houses(value, [first_column, second_column]))
+        {
+            return None;
+        }
+
+        let target_options = self.options(&target_cells);
+        let mut eliminations: Vec<Candidate> = target_cells
+            .iter()
+            .flat_map(|cell_index| {
+                (self.sudoku.cell(*cell_index).options - values)
+                    .iter()
+                    .map(|value| Candidate::new(*cell_index, value))
+            })
+            .collect();
+        eliminations.extend(base.iter().flat_map(|cell_index| {
+            (self.sudoku.cell(*cell_index).options - target_options)
+                .iter()
+                .map(|value| Candidate::new(*cell_index, value))
+        }));
+
+        if eliminations.is_empty() {
+            None
+        } else {
+            eliminations.sort();
+            Some(Step {
+                technique: "Junior Exocet",
+                placements: Vec::new(),
+                eliminations,
+                evidence: Evidence::Exocet {
+                    base: base.to_vec(),
+                    targets: target_cells.to_vec(),
+                    values,
+                },
+            })
+        }
+    }
+
+    /// Outside the band, the cells on the cross lines with the value as option or value can be
+    /// covered by two rows, or by a row and a cross line. Then one of the cross lines has the
+    /// value inside the band, which can only be at a target.
+    fn covered_by_two_houses(&self, value: usize, target_columns: [usize; 2]) -> bool {
+        let band = self.base_row / GRID_SQUARE_SIDE;
+        let cross_columns = [target_columns[0], target_columns[1], self.cross_column];
+        let occurrences: Vec<(usize, usize)> = (0..GRID_ROWS)
+            .filter(|row_nr| row_nr / GRID_SQUARE_SIDE != band)
+            .flat_map(|row_nr| cross_columns.map(|column_nr| (row_nr, column_nr)))
+            .filter(|(row_nr, column_nr)| {
+                let cell = self
+                    .sudoku
+                    .cell(self.orientation.cell_index(*row_nr, *column_nr));
+                cell.can_choose(value) || cell.has_value(value)
+            })
+            .collect();
+
+        let houses: Vec<House> = (0..GRID_ROWS)
+            .map(House::Row)
+            .chain(cross_columns.map(House::Column))
+            .collect();
+        houses.iter().enumerate().any(|(nr, one)| {
+            houses[nr..].iter().any(|other| {
+                occurrences
+                    .iter()
+                    .all(|occurrence| one.covers(*occurrence) || other.covers(*occurrence))
+            })
+        })
+    }
+
+    fn options(&self, cells: &[usize]) -> Candidates {
+        cells.iter().fold(Candidates::NONE, |options, cell_index| {
+            options | self.sudoku.cell(*cell_index).options
+        })
+    }
+}
+
+enum House {
+    Row(usize),
+    Column(usize),
+}
+
+impl House {
+    fn covers(&self, (row_nr, column_nr): (usize, usize)) -> bool {
+        match self {
+            House::Row(nr) => *nr == row_nr,
+            House::Column(nr) => *nr == column_nr,
+        }
+    }
+}
+
+fn columns_of(stack: usize) -> impl Iterator<Item = usize> {
+    stack * GRID_SQUARE_SIDE..(stack + 1) * GRID_SQUARE_SIDE
+}
+
+#[cfg(test)]
+use crate::techniques::with_options;
+
+#[cfg(test)]
+#[test]
+fn junior_exocet_in_top_band() -> crate::SudokuResult {
+    // base r1c1 {1,2} and r1c2 {2,3}, targets r2c4 and r3c7, companions r3c4 and r2c7 without
+    // 1, 2 or 3, and below the band 1, 2 and 3 only in rows 4 and 5 of columns 3, 4 and 7
+    let no_base_values: &[usize] = &[4, 5, 6, 7, 8, 9];
+    let mut options: Vec<(usize, &[usize])> = vec![
+        (0, &[1, 2]),
+        (1, &[2, 3]),
+        (21, no_base_values),
+        (15, no_base_values),
+    ];
+    for row_nr in 5..GRID_ROWS {
+        for column_nr in [2, 3, 6] {
+            options.push((row_nr * GRID_COLUMNS + column_nr, no_base_values));
+        }
+    }
+    let sudoku = with_options(&options)?;
+
+    let step = junior_exocet(&sudoku).expect("a junior exocet");
+    assert_eq!(
+        step.evidence,
+        Evidence::Exocet {
+            base: vec![0, 1],
+            targets: vec![12, 24],
+            values: [1, 2, 3].into_iter().collect(),
+        }
+    );
+    assert_eq!(step.eliminations.len(), 12);
+    assert!(step.eliminations.contains(&Candidate::new(12, 4)));
+    assert!(step.eliminations.contains(&Candidate::new(24, 9)));
+    Ok(())
+}
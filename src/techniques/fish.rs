@@ -0,0 +1,339 @@
+use crate::{
+    techniques::combinations, Candidate, Evidence, Group, Step, Sudoku, GRID_COLUMNS, GRID_ROWS,
+    GRID_SQUARE_SIDE,
+};
+
+/// Rows or columns as base, the other as cover
+#[derive(Clone, Copy)]
+enum Orientation {
+    Rows,
+    Columns,
+}
+
+impl Orientation {
+    fn cell_index(self, base_nr: usize, cover_nr: usize) -> usize {
+        match self {
+            Orientation::Rows => base_nr * GRID_COLUMNS + cover_nr,
+            Orientation::Columns => cover_nr * GRID_COLUMNS + base_nr,
+        }
+    }
+
+    fn base(self, nr: usize) -> Group {
+        match self {
+            Orientation::Rows => Group::Row(nr),
+            Orientation::Columns => Group::Column(nr),
+        }
+    }
+
+    fn cover(self, nr: usize) -> Group {
+        match self {
+            Orientation::Rows => Group::Column(nr),
+            Orientation::Columns => Group::Row(nr),
+        }
+    }
+}
+
+/// N rows in which the options for a value lie in the same N columns: one of those cells in each
+/// column gets the value, so it can be removed from the rest of those columns. Likewise with
+/// the roles of rows and columns swapped.
+pub(crate) fn basic_fish(sudoku: &Sudoku, size: usize) -> Option<Step> {
+    any_fish(sudoku, size, false)
+}
+
+/// A fish with extra options, the fins, outside the cover lines but all in one square. Either
+/// a fin or the fish holds the value, so only cells that see all fins lose it. When a base line
+/// keeps a single option in the covers, the fish is called sashimi, which is still found as a
+/// finned fish.
+pub(crate) fn finned_fish(sudoku: &Sudoku, size: usize) -> Option<Step> {
+    any_fish(sudoku, size, true)
+}
+
+fn any_fish(sudoku: &Sudoku, size: usize, finned: bool) -> Option<Step> {
+    (1..=GRID_COLUMNS).find_map(|value| {
+        [Orientation::Rows, Orientation::Columns]
+            .into_iter()
+            .find_map(|orientation| {
+                Fish {
+                    sudoku,
+                    value,
+                    size,
+                    orientation,
+                    positions: positions(sudoku, value, orientation),
+                }
+                .find(finned)
+            })
+    })
+}
+
+struct Fish<'s> {
+    sudoku: &'s Sudoku,
+    value: usize,
+    size: usize,
+    orientation: Orientation,
+    positions: [u16; GRID_ROWS],
+}
+
+impl Fish<'_> {
+    fn find(&self, finned: bool) -> Option<Step> {
+        let max_positions = if finned {
+            self.size + GRID_SQUARE_SIDE
+        } else {
+            self.size
+        };
+        let candidate_bases: Vec<usize> = (0..GRID_ROWS)
+            .filter(|base_nr| {
+                let count = self.positions[*base_nr].count_ones() as usize;
+                count > 0 && count <= max_positions
+            })
+            .collect();
+
+        combinations(&candidate_bases, self.size)
+            .into_iter()
+            .find_map(|bases| {
+                let covered = bases
+                    .iter()
+                    .fold(0_u16, |covered, base_nr| covered | self.positions[*base_nr]);
+                let covered_count = covered.count_ones() as usize;
+
+                if !finned {
+                    if covered_count == self.size {
+                        self.step(&bases, covered, &[])
+                    } else {
+                        None
+                    }
+                } else if covered_count > self.size {
+                    combinations(&lines(covered), self.size)
+                        .into_iter()
+                        .find_map(|covers| self.finned_step(&bases, mask(&covers)))
+                } else {
+                    None
+                }
+            })
+    }
+
+    fn finned_step(&self, bases: &[usize], covers: u16) -> Option<Step> {
+        if bases
+            .iter()
+            .any(|base_nr| self.positions[*base_nr] & covers == 0)
+        {
+            return None;
+        }
+
+        let fins: Vec<usize> = bases
+            .iter()
+            .flat_map(|base_nr| {
+                lines(self.positions[*base_nr] & !covers)
+                    .into_iter()
+                    .map(|cover_nr| self.orientation.cell_index(*base_nr, cover_nr))
+            })
+            .collect();
+        let fin_square = self.sudoku.cell(fins[0]).square_nr;
+
+        if fins
+            .iter()
+            .all(|fin| self.sudoku.cell(*fin).square_nr == fin_square)
+        {
+            self.step(bases, covers, &fins)
+        } else {
+            None
+        }
+    }
+
+    fn step(&self, bases: &[usize], covers: u16, fins: &[usize]) -> Option<Step> {
+        let fin_square = fins.first().map(|fin| self.sudoku.cell(*fin).square_nr);
+        let eliminations: Vec<Candidate> = lines(covers)
+            .into_iter()
+            .flat_map(|cover_nr| {
+                (0..GRID_ROWS)
+                    .filter(|base_nr| {
+                        !bases.contains(base_nr) && self.positions[*base_nr] & (1 << cover_nr) != 0
+                    })
+                    .map(|base_nr| self.orientation.cell_index(base_nr, cover_nr))
+                    .filter(|cell_index| {
+                        fin_square.is_none()
+                            || fin_square == Some(self.sudoku.cell(*cell_index).square_nr)
+                    })
+                    .map(|cell_index| Candidate::new(cell_index, self.value))
+                    .collect::<Vec<Candidate>>()
+            })
+            .collect();
+
+        if eliminations.is_empty() {
+            return None;
+        }
+
+        Some(Step {
+            technique: fish_name(self.size, !fins.is_empty()),
+            placements: Vec::new(),
+            eliminations,
+            evidence: Evidence::Fish {
+                value: self.value,
+                base: bases.iter().map(|nr| self.orientation.base(*nr)).collect(),
+                cover: lines(covers)
+                    .into_iter()
+                    .map(|nr| self.orientation.cover(nr))
+                    .collect(),
+                fins: fins.to_vec(),
+            },
+        })
+    }
+}
+
+fn lines(mask: u16) -> Vec<usize> {
+    (0..GRID_COLUMNS)
+        .filter(|nr| mask & (1 << nr) != 0)
+        .collect()
+}
+
+fn mask(lines: &[usize]) -> u16 {
+    lines.iter().fold(0, |mask, nr| mask | (1 << nr))
+}
+
+/// Per base line, bit n is set when the cell on cover line n has the value as option
+fn positions(sudoku: &Sudoku, value: usize, orientation: Orientation) -> [u16; GRID_ROWS] {
+    let mut positions = [0_u16; GRID_ROWS];
+
+    for (base_nr, base_positions) in positions.iter_mut().enumerate() {
+        for cover_nr in 0..GRID_COLUMNS {
+            if sudoku
+                .cell(orientation.cell_index(base_nr, cover_nr))
+                .can_choose(value)
+            {
+                *base_positions |= 1 << cover_nr;
+            }
+        }
+    }
+
+    positions
+}
+
+fn fish_name(size: usize, finned: bool) -> &'static str {
+    match (size, finned) {
+        (2, false) => "X-Wing",
+        (3, false) => "Swordfish",
+        (_, false) => "Jellyfish",
+        (2, true) => "Finned X-Wing",
+        (3, true) => "Finned Swordfish",
+        (_, true) => "Finned Jellyfish",
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn x_wing_on_rows() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    // in rows 1 and 5, 7 can only go to columns 2 and 6
+    for cover_nr in [0, 2, 3, 4, 6, 7, 8] {
+        sudoku.eliminate_option(cover_nr, 7)?;
+        sudoku.eliminate_option(4 * GRID_COLUMNS + cover_nr, 7)?;
+    }
+
+    let step = basic_fish(&sudoku, 2).expect("an X-Wing");
+    assert_eq!(step.technique, "X-Wing");
+    assert_eq!(
+        step.evidence,
+        Evidence::Fish {
+            value: 7,
+            base: vec![Group::Row(0), Group::Row(4)],
+            cover: vec![Group::Column(1), Group::Column(5)],
+            fins: vec![],
+        }
+    );
+    assert_eq!(step.eliminations.len(), 14);
+    assert!(step.eliminations.contains(&Candidate::new(10, 7)));
+    assert!(step.eliminations.contains(&Candidate::new(77, 7)));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn swordfish_on_columns() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    // in columns 1, 4 and 7, 3 can only go to rows 2, 5 and 8
+    for column_nr in [0, 3, 6] {
+        for row_nr in [0, 2, 3, 5, 6, 8] {
+            sudoku.eliminate_option(row_nr * GRID_COLUMNS + column_nr, 3)?;
+        }
+    }
+
+    let step = basic_fish(&sudoku, 3).expect("a Swordfish");
+    assert_eq!(step.technique, "Swordfish");
+    assert_eq!(
+        step.evidence,
+        Evidence::Fish {
+            value: 3,
+            base: vec![Group::Column(0), Group::Column(3), Group::Column(6)],
+            cover: vec![Group::Row(1), Group::Row(4), Group::Row(7)],
+            fins: vec![],
+        }
+    );
+    assert_eq!(step.eliminations.len(), 18);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn finned_x_wing() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    // in row 1, 7 can go to columns 2 and 6, in row 5 to columns 2, 6 and the fin in column 4
+    for cover_nr in [0, 2, 3, 4, 6, 7, 8] {
+        sudoku.eliminate_option(cover_nr, 7)?;
+    }
+    for cover_nr in [0, 2, 3, 6, 7, 8] {
+        sudoku.eliminate_option(4 * GRID_COLUMNS + cover_nr, 7)?;
+    }
+
+    assert_eq!(basic_fish(&sudoku, 2), None);
+
+    let step = finned_fish(&sudoku, 2).expect("a finned X-Wing");
+    assert_eq!(step.technique, "Finned X-Wing");
+    assert_eq!(
+        step.evidence,
+        Evidence::Fish {
+            value: 7,
+            base: vec![Group::Row(0), Group::Row(4)],
+            cover: vec![Group::Column(1), Group::Column(5)],
+            fins: vec![40],
+        }
+    );
+    // only the cells of column 6 in the fin's square see the fin
+    assert_eq!(
+        step.eliminations,
+        vec![Candidate::new(32, 7), Candidate::new(50, 7)]
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn sashimi_x_wing() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    // in row 5 only column 6 remains in the covers, next to fins in columns 4 and 5
+    for cover_nr in [0, 2, 3, 4, 6, 7, 8] {
+        sudoku.eliminate_option(cover_nr, 7)?;
+    }
+    for cover_nr in [0, 1, 2, 6, 7, 8] {
+        sudoku.eliminate_option(4 * GRID_COLUMNS + cover_nr, 7)?;
+    }
+
+    let step = finned_fish(&sudoku, 2).expect("a sashimi X-Wing");
+    assert_eq!(step.technique, "Finned X-Wing");
+    assert_eq!(
+        step.evidence,
+        Evidence::Fish {
+            value: 7,
+            base: vec![Group::Row(0), Group::Row(4)],
+            cover: vec![Group::Column(1), Group::Column(5)],
+            fins: vec![39, 40],
+        }
+    );
+    assert_eq!(
+        step.eliminations,
+        vec![Candidate::new(32, 7), Candidate::new(50, 7)]
+    );
+    Ok(())
+}
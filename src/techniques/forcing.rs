@@ -0,0 +1,225 @@
+use crate::{
+    Branch, Candidate, Evidence, Group, GroupOptions, Step, Sudoku, SudokuError, GRID_COLUMNS,
+};
+
+/// Places the assumption in a copy of the puzzle and applies singles, until they get stuck or
+/// run into a contradiction
+fn assume(sudoku: &Sudoku, assumption: Candidate) -> Option<(Branch, Sudoku)> {
+    let mut assumed = sudoku.clone();
+    let contradiction = match assumed
+        .choose(assumption.cell_index, assumption.value)
+        .and_then(|_| assumed.solve_singles())
+    {
+        Ok(_) => None,
+        Err(SudokuError::Contradiction(contradiction)) => Some(contradiction),
+        Err(_) => return None,
+    };
+
+    let placements = sudoku
+        .open_cells()
+        .filter(|cell| cell.index != assumption.cell_index)
+        .filter_map(|cell| {
+            assumed
+                .cell(cell.index)
+                .value
+                .map(|value| Candidate::new(cell.index, value))
+        })
+        .collect();
+
+    Some((
+        Branch {
+            assumption,
+            placements,
+            contradiction,
+        },
+        assumed,
+    ))
+}
+
+/// Follows each assumption. One that leads to a contradiction is eliminated; otherwise, what
+/// all of them lead to must be true.
+fn forcing_step(
+    sudoku: &Sudoku,
+    technique: &'static str,
+    assumptions: impl Iterator<Item = Candidate>,
+) -> Option<Step> {
+    let (branches, outcomes): (Vec<Branch>, Vec<Sudoku>) = assumptions
+        .map(|assumption| assume(sudoku, assumption))
+        .collect::<Option<Vec<(Branch, Sudoku)>>>()?
+        .into_iter()
+        .unzip();
+
+    if let Some(failed) = branches
+        .iter()
+        .find(|branch| branch.contradiction.is_some())
+    {
+        return Some(Step {
+            technique,
+            placements: Vec::new(),
+            eliminations: vec![failed.assumption],
+            evidence: Evidence::Forcing {
+                branches: vec![failed.clone()],
+            },
+        });
+    }
+
+    let placements: Vec<Candidate> = sudoku
+        .open_cells()
+        .filter_map(|cell| {
+            let value = outcomes[0].cell(cell.index).value?;
+            if outcomes
+                .iter()
+                .all(|outcome| outcome.cell(cell.index).value == Some(value))
+            {
+                Some(Candidate::new(cell.index, value))
+            } else {
+                None
+            }
+        })
+        .collect();
+    let eliminations: Vec<Candidate> = if placements.is_empty() {
+        sudoku
+            .open_cells()
+            .flat_map(|cell| {
+                cell.available_options()
+                    .map(move |value| Candidate::new(cell.index, value))
+            })
+            .filter(|candidate| {
+                outcomes.iter().all(|outcome| {
+                    let cell = outcome.cell(candidate.cell_index);
+                    !cell.can_choose(candidate.value) && !cell.has_value(candidate.value)
+                })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    if placements.is_empty() && eliminations.is_empty() {
+        None
+    } else {
+        Some(Step {
+            technique,
+            placements,
+            eliminations,
+            evidence: Evidence::Forcing { branches },
+        })
+    }
+}
+
+/// Each option of a cell with two options, followed through singles
+pub(crate) fn cell_forcing_chain(sudoku: &Sudoku) -> Option<Step> {
+    sudoku
+        .open_cells()
+        .filter(|cell| cell.todo_count == 2)
+        .find_map(|cell| {
+            forcing_step(
+                sudoku,
+                "Cell forcing chain",
+                cell.available_options()
+                    .map(|value| Candidate::new(cell.index, value)),
+            )
+        })
+}
+
+/// Each place for a value in a group with two or three places left, followed through singles
+pub(crate) fn region_forcing_chain(sudoku: &Sudoku) -> Option<Step> {
+    Group::all().find_map(|group| {
+        let group_options = GroupOptions::of(sudoku, group);
+
+        (1..=GRID_COLUMNS).find_map(|value| {
+            let cells = group_options.cells_for_any(value.into());
+            if !(2..=3).contains(&cells.count_ones()) {
+                return None;
+            }
+
+            forcing_step(
+                sudoku,
+                "Region forcing chain",
+                crate::indices(cells).map(|cell_index| Candidate::new(cell_index, value)),
+            )
+        })
+    })
+}
+
+/// Any option that leads to a contradiction through singles is eliminated
+pub(crate) fn nishio(sudoku: &Sudoku) -> Option<Step> {
+    sudoku
+        .open_cells()
+        .flat_map(|cell| {
+            cell.available_options()
+                .map(move |value| Candidate::new(cell.index, value))
+        })
+        .find_map(|assumption| {
+            let (branch, _) = assume(sudoku, assumption)?;
+            branch.contradiction?;
+
+            Some(Step {
+                technique: "Nishio",
+                placements: Vec::new(),
+                eliminations: vec![assumption],
+                evidence: Evidence::Forcing {
+                    branches: vec![branch],
+                },
+            })
+        })
+}
+
+#[cfg(test)]
+use crate::techniques::{keep_only, with_options};
+
+#[cfg(test)]
+#[test]
+fn cell_forcing_chain_elimination() -> crate::SudokuResult {
+    // r1c1 {1,2}: 1 makes r1c2 {1,5} a 5, 2 makes r2c1 {2,5} a 5
+    let sudoku = with_options(&[(0, &[1, 2]), (1, &[1, 5]), (9, &[2, 5])])?;
+
+    let step = cell_forcing_chain(&sudoku).expect("a cell forcing chain");
+    let Evidence::Forcing { branches } = &step.evidence else {
+        panic!("forcing branches")
+    };
+    assert_eq!(
+        branches
+            .iter()
+            .map(|branch| branch.assumption)
+            .collect::<Vec<Candidate>>(),
+        vec![Candidate::new(0, 1), Candidate::new(0, 2)]
+    );
+    assert!(branches[0].placements.contains(&Candidate::new(1, 5)));
+    assert!(branches[1].placements.contains(&Candidate::new(9, 5)));
+    assert!(step.placements.is_empty());
+    assert!(step.eliminations.contains(&Candidate::new(2, 5)));
+    assert!(step.eliminations.contains(&Candidate::new(20, 5)));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn region_forcing_chain_elimination() -> crate::SudokuResult {
+    // 7 in row 1 at r1c1 or r1c9, and r9c1 and r9c9 both {3,7}
+    let mut sudoku = with_options(&[(72, &[3, 7]), (80, &[3, 7])])?;
+    keep_only(&mut sudoku, Group::Row(0), 7, &[0, 8])?;
+
+    let step = region_forcing_chain(&sudoku).expect("a region forcing chain");
+    assert_eq!(step.technique, "Region forcing chain");
+    // both ways, 3 and 7 end up in r9c1 and r9c9, and 7 in columns 1 and 9 of rows 1 and 9
+    assert_eq!(step.eliminations.len(), 28);
+    assert!(step.eliminations.contains(&Candidate::new(36, 7)));
+    assert!(step.eliminations.contains(&Candidate::new(76, 3)));
+    assert!(step.eliminations.contains(&Candidate::new(76, 7)));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn nishio_contradiction() -> crate::SudokuResult {
+    let sudoku = with_options(&[(0, &[1, 2]), (1, &[1, 2]), (2, &[1, 2, 3])])?;
+
+    let step = nishio(&sudoku).expect("a contradiction");
+    assert_eq!(step.eliminations, vec![Candidate::new(2, 1)]);
+    let Evidence::Forcing { branches } = step.evidence else {
+        panic!("forcing branches")
+    };
+    assert!(branches[0].contradiction.is_some());
+    Ok(())
+}
@@ -0,0 +1,291 @@
+use crate::{
+    indices,
+    techniques::{combinations, subsets::eliminations_outside},
+    Candidate, Candidates, Evidence, Group, GroupOptions, Step, Sudoku, GRID_COLUMNS,
+};
+
+/// The options for a value in a square all lie on one row or column: the value can be removed
+/// from the rest of that line
+pub(crate) fn pointing(sudoku: &Sudoku) -> Option<Step> {
+    (0..GRID_COLUMNS).find_map(|square_nr| {
+        let square = Group::Square(square_nr);
+
+        locked_in(sudoku, square, |cell_indices| {
+            let cell = sudoku.cell(cell_indices[0]);
+            if cell_indices
+                .iter()
+                .all(|index| sudoku.cell(*index).row == cell.row)
+            {
+                Some(Group::Row(cell.row))
+            } else if cell_indices
+                .iter()
+                .all(|index| sudoku.cell(*index).column == cell.column)
+            {
+                Some(Group::Column(cell.column))
+            } else {
+                None
+            }
+        })
+        .map(|(value, line, cells, eliminations)| Step {
+            technique: "Pointing",
+            placements: Vec::new(),
+            eliminations,
+            evidence: Evidence::Intersection {
+                value,
+                from: square,
+                to: line,
+                cells,
+            },
+        })
+    })
+}
+
+/// The options for a value in a row or column all lie in one square: the value can be removed
+/// from the rest of that square
+pub(crate) fn claiming(sudoku: &Sudoku) -> Option<Step> {
+    (0..GRID_COLUMNS)
+        .map(Group::Row)
+        .chain((0..GRID_COLUMNS).map(Group::Column))
+        .find_map(|line| {
+            locked_in(sudoku, line, |cell_indices| {
+                let square_nr = sudoku.cell(cell_indices[0]).square_nr;
+                if cell_indices
+                    .iter()
+                    .all(|index| sudoku.cell(*index).square_nr == square_nr)
+                {
+                    Some(Group::Square(square_nr))
+                } else {
+                    None
+                }
+            })
+            .map(|(value, square, cells, eliminations)| Step {
+                technique: "Claiming",
+                placements: Vec::new(),
+                eliminations,
+                evidence: Evidence::Intersection {
+                    value,
+                    from: line,
+                    to: square,
+                    cells,
+                },
+            })
+        })
+}
+
+/// Two or three cells where a line crosses a square, with at least two more options than cells.
+/// When cells from the rest of the line and from the rest of the square, with no options in
+/// common, make up the difference, all those cells together hold exactly their values. The rest
+/// of the line loses the values that can't go to the square cells, and the rest of the square
+/// the values that can't go to the line cells.
+pub(crate) fn sue_de_coq(sudoku: &Sudoku) -> Option<Step> {
+    (0..GRID_COLUMNS)
+        .map(Group::Row)
+        .chain((0..GRID_COLUMNS).map(Group::Column))
+        .find_map(|line| {
+            let mut squares: Vec<usize> =
+                sudoku.cells_of(line).map(|cell| cell.square_nr).collect();
+            squares.dedup();
+
+            squares
+                .into_iter()
+                .find_map(|square_nr| sue_de_coq_in(sudoku, line, Group::Square(square_nr)))
+        })
+}
+
+fn sue_de_coq_in(sudoku: &Sudoku, line: Group, square: Group) -> Option<Step> {
+    let open_cells = |group| -> Vec<usize> {
+        sudoku
+            .cells_of(group)
+            .filter(|cell| cell.has_options())
+            .map(|cell| cell.index)
+            .collect()
+    };
+    let (open_line, open_square) = (open_cells(line), open_cells(square));
+    let crossing: Vec<usize> = open_line
+        .iter()
+        .copied()
+        .filter(|cell_index| open_square.contains(cell_index))
+        .collect();
+    let line_rest: Vec<usize> = open_line
+        .iter()
+        .copied()
+        .filter(|cell_index| !crossing.contains(cell_index))
+        .collect();
+    let square_rest: Vec<usize> = open_square
+        .iter()
+        .copied()
+        .filter(|cell_index| !crossing.contains(cell_index))
+        .collect();
+    let options = |cells: &[usize]| {
+        cells.iter().fold(Candidates::NONE, |values, cell_index| {
+            values | sudoku.cell(*cell_index).options
+        })
+    };
+
+    (2..=crossing.len()).find_map(|size| {
+        combinations(&crossing, size).into_iter().find_map(|cells| {
+            let values = options(&cells);
+            if values.len() < size + 2 {
+                return None;
+            }
+
+            subsets_of(&line_rest).into_iter().find_map(|line_cells| {
+                let line_values = options(&line_cells);
+
+                subsets_of(&square_rest)
+                    .into_iter()
+                    .find_map(|square_cells| {
+                        let square_values = options(&square_cells);
+                        let cell_count = size + line_cells.len() + square_cells.len();
+                        if !(line_values & square_values).is_empty()
+                            || (values | line_values | square_values).len() != cell_count
+                        {
+                            return None;
+                        }
+
+                        let in_line: Vec<usize> =
+                            cells.iter().chain(&line_cells).copied().collect();
+                        let in_square: Vec<usize> =
+                            cells.iter().chain(&square_cells).copied().collect();
+                        let mut eliminations = eliminations_outside(
+                            sudoku,
+                            &open_line,
+                            &in_line,
+                            line_values | (values - square_values),
+                        );
+                        eliminations.extend(eliminations_outside(
+                            sudoku,
+                            &open_square,
+                            &in_square,
+                            square_values | (values - line_values),
+                        ));
+                        eliminations.sort();
+                        eliminations.dedup();
+
+                        if eliminations.is_empty() {
+                            None
+                        } else {
+                            Some(Step {
+                                technique: "Sue de Coq",
+                                placements: Vec::new(),
+                                eliminations,
+                                evidence: Evidence::SueDeCoq {
+                                    line,
+                                    square,
+                                    cells: cells.clone(),
+                                    line_cells: line_cells.clone(),
+                                    square_cells,
+                                },
+                            })
+                        }
+                    })
+            })
+        })
+    })
+}
+
+/// The ways to pick one to three of the cells
+fn subsets_of(cells: &[usize]) -> Vec<Vec<usize>> {
+    (1..=cells.len().min(3))
+        .flat_map(|size| combinations(cells, size))
+        .collect()
+}
+
+/// Finds a value whose options in the group all lie within the group returned by `other_group`,
+/// where that other group still has the value as option outside the first group
+fn locked_in(
+    sudoku: &Sudoku,
+    group: Group,
+    other_group: impl Fn(&[usize]) -> Option<Group>,
+) -> Option<(usize, Group, Vec<usize>, Vec<Candidate>)> {
+    let group_options = GroupOptions::of(sudoku, group);
+
+    group_options.values().iter().find_map(|value| {
+        let cells: Vec<usize> =
+            indices(group_options.cells_for_any(Candidates::single(value))).collect();
+        let other = other_group(&cells)?;
+
+        let eliminations: Vec<Candidate> = sudoku
+            .cells_of(other)
+            .filter(|cell| !cells.contains(&cell.index) && cell.can_choose(value))
+            .map(|cell| Candidate::new(cell.index, value))
+            .collect();
+
+        if eliminations.is_empty() {
+            None
+        } else {
+            Some((value, other, cells, eliminations))
+        }
+    })
+}
+
+#[cfg(test)]
+#[test]
+fn pointing_and_claiming() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    // 1 only fits in the top row of the first square
+    for cell_index in [9, 10, 11, 18, 19, 20] {
+        sudoku.eliminate_option(cell_index, 1)?;
+    }
+
+    let step = pointing(&sudoku).expect("pointing 1");
+    assert_eq!(
+        step.evidence,
+        Evidence::Intersection {
+            value: 1,
+            from: Group::Square(0),
+            to: Group::Row(0),
+            cells: vec![0, 1, 2]
+        }
+    );
+    assert_eq!(step.eliminations.len(), 6);
+
+    // in the bottom row 2 only fits in the last square
+    let mut sudoku = Sudoku::new();
+    for cell_index in 72..78 {
+        sudoku.eliminate_option(cell_index, 2)?;
+    }
+
+    let step = claiming(&sudoku).expect("claiming 2");
+    assert_eq!(
+        step.evidence,
+        Evidence::Intersection {
+            value: 2,
+            from: Group::Row(8),
+            to: Group::Square(8),
+            cells: vec![78, 79, 80]
+        }
+    );
+    assert_eq!(step.eliminations.len(), 6);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn sue_de_coq_in_top_row() -> crate::SudokuResult {
+    // r1c1 {1,2,3} and r1c2 {2,3,4}, with r1c6 {1,2} on the row and r2c2 {3,4} in the square
+    let sudoku = crate::techniques::with_options(&[
+        (0, &[1, 2, 3]),
+        (1, &[2, 3, 4]),
+        (5, &[1, 2]),
+        (10, &[3, 4]),
+    ])?;
+
+    let step = sue_de_coq(&sudoku).expect("a Sue de Coq");
+    assert_eq!(
+        step.evidence,
+        Evidence::SueDeCoq {
+            line: Group::Row(0),
+            square: Group::Square(0),
+            cells: vec![0, 1],
+            line_cells: vec![5],
+            square_cells: vec![10],
+        }
+    );
+    assert_eq!(step.eliminations.len(), 24);
+    assert!(step.eliminations.contains(&Candidate::new(8, 1)));
+    assert!(step.eliminations.contains(&Candidate::new(20, 4)));
+    assert!(!step.eliminations.contains(&Candidate::new(8, 3)));
+    Ok(())
+}
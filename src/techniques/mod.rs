@@ -0,0 +1,422 @@
+mod als;
+mod chains;
+mod coloring;
+mod exocet;
+mod fish;
+mod forcing;
+mod intersections;
+mod single_digit;
+mod singles;
+mod subsets;
+mod uniqueness;
+mod wings;
+
+use crate::{indices, Candidate, Group, GroupOptions, Step, Sudoku, Technique};
+
+/// The techniques that come with the crate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuiltIn {
+    HiddenSingle,
+    Pointing,
+    Claiming,
+    NakedPair,
+    XWing,
+    HiddenPair,
+    NakedTriple,
+    Swordfish,
+    HiddenTriple,
+    Skyscraper,
+    TwoStringKite,
+    EmptyRectangle,
+    XYWing,
+    XYZWing,
+    WWing,
+    NakedQuad,
+    Jellyfish,
+    HiddenQuad,
+    FinnedXWing,
+    FinnedSwordfish,
+    FinnedJellyfish,
+    SimpleColoring,
+    MultiColoring,
+    XChain,
+    XYChain,
+    AlternatingInferenceChain,
+    UniqueRectangleType1,
+    UniqueRectangleType2,
+    UniqueRectangleType3,
+    UniqueRectangleType4,
+    UniqueRectangleType5,
+    UniqueRectangleType6,
+    BivalueUniversalGrave,
+    AlsXZ,
+    AlsXYWing,
+    DeathBlossom,
+    SueDeCoq,
+    // the last resort, see LAST_RESORT
+    CellForcingChain,
+    RegionForcingChain,
+    Nishio,
+    /// Not part of any of the lists: a pattern for the hardest puzzles, which a strategy only
+    /// looks for when it is added
+    JuniorExocet,
+}
+
+impl BuiltIn {
+    /// Simplest first
+    pub const DEFAULT: &'static [BuiltIn] = &[
+        BuiltIn::HiddenSingle,
+        BuiltIn::Pointing,
+        BuiltIn::Claiming,
+        BuiltIn::NakedPair,
+        BuiltIn::XWing,
+        BuiltIn::HiddenPair,
+        BuiltIn::NakedTriple,
+        BuiltIn::Swordfish,
+        BuiltIn::HiddenTriple,
+        BuiltIn::Skyscraper,
+        BuiltIn::TwoStringKite,
+        BuiltIn::EmptyRectangle,
+        BuiltIn::XYWing,
+        BuiltIn::XYZWing,
+        BuiltIn::WWing,
+        BuiltIn::SueDeCoq,
+        BuiltIn::NakedQuad,
+        BuiltIn::Jellyfish,
+        BuiltIn::HiddenQuad,
+        BuiltIn::FinnedXWing,
+        BuiltIn::FinnedSwordfish,
+        BuiltIn::FinnedJellyfish,
+        BuiltIn::SimpleColoring,
+        BuiltIn::MultiColoring,
+        BuiltIn::XChain,
+        BuiltIn::XYChain,
+        BuiltIn::AlternatingInferenceChain,
+        BuiltIn::AlsXZ,
+        BuiltIn::AlsXYWing,
+        BuiltIn::DeathBlossom,
+    ];
+
+    /// Techniques that assume the puzzle has a single solution, and may eliminate the actual
+    /// solution of a puzzle that doesn't
+    pub const UNIQUENESS: &'static [BuiltIn] = &[
+        BuiltIn::UniqueRectangleType1,
+        BuiltIn::UniqueRectangleType2,
+        BuiltIn::UniqueRectangleType4,
+        BuiltIn::UniqueRectangleType3,
+        BuiltIn::UniqueRectangleType5,
+        BuiltIn::UniqueRectangleType6,
+        BuiltIn::BivalueUniversalGrave,
+    ];
+
+    /// The default techniques with the uniqueness techniques in between, simplest first
+    pub const DEFAULT_ASSUMING_UNIQUE: &'static [BuiltIn] = &[
+        BuiltIn::HiddenSingle,
+        BuiltIn::Pointing,
+        BuiltIn::Claiming,
+        BuiltIn::NakedPair,
+        BuiltIn::XWing,
+        BuiltIn::HiddenPair,
+        BuiltIn::NakedTriple,
+        BuiltIn::Swordfish,
+        BuiltIn::HiddenTriple,
+        BuiltIn::Skyscraper,
+        BuiltIn::TwoStringKite,
+        BuiltIn::EmptyRectangle,
+        BuiltIn::XYWing,
+        BuiltIn::XYZWing,
+        BuiltIn::WWing,
+        BuiltIn::UniqueRectangleType1,
+        BuiltIn::UniqueRectangleType2,
+        BuiltIn::UniqueRectangleType4,
+        BuiltIn::UniqueRectangleType3,
+        BuiltIn::UniqueRectangleType5,
+        BuiltIn::UniqueRectangleType6,
+        BuiltIn::SueDeCoq,
+        BuiltIn::NakedQuad,
+        BuiltIn::Jellyfish,
+        BuiltIn::HiddenQuad,
+        BuiltIn::FinnedXWing,
+        BuiltIn::FinnedSwordfish,
+        BuiltIn::BivalueUniversalGrave,
+        BuiltIn::FinnedJellyfish,
+        BuiltIn::SimpleColoring,
+        BuiltIn::MultiColoring,
+        BuiltIn::XChain,
+        BuiltIn::XYChain,
+        BuiltIn::AlternatingInferenceChain,
+        BuiltIn::AlsXZ,
+        BuiltIn::AlsXYWing,
+        BuiltIn::DeathBlossom,
+    ];
+
+    /// Techniques that follow options through singles, which the default strategies try once
+    /// the others find nothing, before the search starts guessing. They are left out of the
+    /// other lists, as following every option costs far more than looking for a pattern.
+    pub const LAST_RESORT: &'static [BuiltIn] = &[
+        BuiltIn::CellForcingChain,
+        BuiltIn::RegionForcingChain,
+        BuiltIn::Nishio,
+    ];
+}
+
+impl Technique for BuiltIn {
+    fn name(&self) -> &'static str {
+        match self {
+            BuiltIn::HiddenSingle => "Hidden single",
+            BuiltIn::Pointing => "Pointing",
+            BuiltIn::Claiming => "Claiming",
+            BuiltIn::NakedPair => "Naked pair",
+            BuiltIn::HiddenPair => "Hidden pair",
+            BuiltIn::NakedTriple => "Naked triple",
+            BuiltIn::HiddenTriple => "Hidden triple",
+            BuiltIn::NakedQuad => "Naked quad",
+            BuiltIn::HiddenQuad => "Hidden quad",
+            BuiltIn::XWing => "X-Wing",
+            BuiltIn::Swordfish => "Swordfish",
+            BuiltIn::Jellyfish => "Jellyfish",
+            BuiltIn::FinnedXWing => "Finned X-Wing",
+            BuiltIn::FinnedSwordfish => "Finned Swordfish",
+            BuiltIn::FinnedJellyfish => "Finned Jellyfish",
+            BuiltIn::XYWing => "XY-Wing",
+            BuiltIn::XYZWing => "XYZ-Wing",
+            BuiltIn::WWing => "W-Wing",
+            BuiltIn::Skyscraper => "Skyscraper",
+            BuiltIn::TwoStringKite => "Two-String Kite",
+            BuiltIn::EmptyRectangle => "Empty Rectangle",
+            BuiltIn::SimpleColoring => "Simple coloring",
+            BuiltIn::MultiColoring => "Multi-coloring",
+            BuiltIn::XChain => "X-Chain",
+            BuiltIn::XYChain => "XY-Chain",
+            BuiltIn::AlternatingInferenceChain => "AIC",
+            BuiltIn::UniqueRectangleType1 => "Unique Rectangle type 1",
+            BuiltIn::UniqueRectangleType2 => "Unique Rectangle type 2",
+            BuiltIn::UniqueRectangleType3 => "Unique Rectangle type 3",
+            BuiltIn::UniqueRectangleType4 => "Unique Rectangle type 4",
+            BuiltIn::UniqueRectangleType5 => "Unique Rectangle type 5",
+            BuiltIn::UniqueRectangleType6 => "Unique Rectangle type 6",
+            BuiltIn::BivalueUniversalGrave => "BUG+1",
+            BuiltIn::AlsXZ => "ALS-XZ",
+            BuiltIn::AlsXYWing => "ALS-XY-Wing",
+            BuiltIn::DeathBlossom => "Death Blossom",
+            BuiltIn::SueDeCoq => "Sue de Coq",
+            BuiltIn::CellForcingChain => "Cell forcing chain",
+            BuiltIn::RegionForcingChain => "Region forcing chain",
+            BuiltIn::Nishio => "Nishio",
+            BuiltIn::JuniorExocet => "Junior Exocet",
+        }
+    }
+
+    /// Roughly the ratings Sudoku Explainer gives these techniques
+    fn difficulty(&self) -> f32 {
+        match self {
+            BuiltIn::HiddenSingle => 1.5,
+            BuiltIn::Pointing => 2.6,
+            BuiltIn::Claiming => 2.8,
+            BuiltIn::NakedPair => 3.0,
+            BuiltIn::XWing => 3.2,
+            BuiltIn::HiddenPair => 3.4,
+            BuiltIn::NakedTriple => 3.6,
+            BuiltIn::Swordfish => 3.8,
+            BuiltIn::HiddenTriple => 4.0,
+            BuiltIn::Skyscraper => 4.0,
+            BuiltIn::TwoStringKite => 4.1,
+            BuiltIn::EmptyRectangle => 4.2,
+            BuiltIn::XYWing => 4.2,
+            BuiltIn::XYZWing => 4.4,
+            BuiltIn::WWing => 4.4,
+            BuiltIn::UniqueRectangleType1 => 4.5,
+            BuiltIn::UniqueRectangleType2 => 4.5,
+            BuiltIn::UniqueRectangleType4 => 4.5,
+            BuiltIn::UniqueRectangleType3 => 4.6,
+            BuiltIn::UniqueRectangleType5 => 4.6,
+            BuiltIn::UniqueRectangleType6 => 4.6,
+            BuiltIn::SueDeCoq => 5.0,
+            BuiltIn::NakedQuad => 5.0,
+            BuiltIn::Jellyfish => 5.2,
+            BuiltIn::HiddenQuad => 5.4,
+            BuiltIn::FinnedXWing => 5.4,
+            BuiltIn::FinnedSwordfish => 5.6,
+            BuiltIn::BivalueUniversalGrave => 5.6,
+            BuiltIn::FinnedJellyfish => 5.8,
+            BuiltIn::SimpleColoring => 6.0,
+            BuiltIn::MultiColoring => 6.2,
+            BuiltIn::XChain => 6.5,
+            BuiltIn::XYChain => 6.6,
+            BuiltIn::AlternatingInferenceChain => 7.0,
+            BuiltIn::AlsXZ => 7.2,
+            BuiltIn::AlsXYWing => 7.4,
+            BuiltIn::DeathBlossom => 7.6,
+            BuiltIn::CellForcingChain => 8.0,
+            BuiltIn::RegionForcingChain => 8.2,
+            BuiltIn::Nishio => 8.5,
+            BuiltIn::JuniorExocet => 9.0,
+        }
+    }
+
+    fn apply(&self, sudoku: &Sudoku) -> Option<Step> {
+        match self {
+            BuiltIn::HiddenSingle => singles::hidden_single(sudoku),
+            BuiltIn::Pointing => intersections::pointing(sudoku),
+            BuiltIn::Claiming => intersections::claiming(sudoku),
+            BuiltIn::NakedPair => subsets::naked_subset(sudoku, 2),
+            BuiltIn::HiddenPair => subsets::hidden_subset(sudoku, 2),
+            BuiltIn::NakedTriple => subsets::naked_subset(sudoku, 3),
+            BuiltIn::HiddenTriple => subsets::hidden_subset(sudoku, 3),
+            BuiltIn::NakedQuad => subsets::naked_subset(sudoku, 4),
+            BuiltIn::HiddenQuad => subsets::hidden_subset(sudoku, 4),
+            BuiltIn::XWing => fish::basic_fish(sudoku, 2),
+            BuiltIn::Swordfish => fish::basic_fish(sudoku, 3),
+            BuiltIn::Jellyfish => fish::basic_fish(sudoku, 4),
+            BuiltIn::FinnedXWing => fish::finned_fish(sudoku, 2),
+            BuiltIn::FinnedSwordfish => fish::finned_fish(sudoku, 3),
+            BuiltIn::FinnedJellyfish => fish::finned_fish(sudoku, 4),
+            BuiltIn::XYWing => wings::xy_wing(sudoku),
+            BuiltIn::XYZWing => wings::xyz_wing(sudoku),
+            BuiltIn::WWing => wings::w_wing(sudoku),
+            BuiltIn::Skyscraper => single_digit::skyscraper(sudoku),
+            BuiltIn::TwoStringKite => single_digit::two_string_kite(sudoku),
+            BuiltIn::EmptyRectangle => single_digit::empty_rectangle(sudoku),
+            BuiltIn::SimpleColoring => coloring::simple_coloring(sudoku),
+            BuiltIn::MultiColoring => coloring::multi_coloring(sudoku),
+            BuiltIn::XChain => chains::chain(sudoku, chains::Links::SingleValue),
+            BuiltIn::XYChain => chains::chain(sudoku, chains::Links::Bivalue),
+            BuiltIn::AlternatingInferenceChain => chains::chain(sudoku, chains::Links::All),
+            BuiltIn::UniqueRectangleType1 => uniqueness::unique_rectangle(sudoku, 1),
+            BuiltIn::UniqueRectangleType2 => uniqueness::unique_rectangle(sudoku, 2),
+            BuiltIn::UniqueRectangleType3 => uniqueness::unique_rectangle(sudoku, 3),
+            BuiltIn::UniqueRectangleType4 => uniqueness::unique_rectangle(sudoku, 4),
+            BuiltIn::UniqueRectangleType5 => uniqueness::unique_rectangle(sudoku, 5),
+            BuiltIn::UniqueRectangleType6 => uniqueness::unique_rectangle(sudoku, 6),
+            BuiltIn::BivalueUniversalGrave => uniqueness::bivalue_universal_grave(sudoku),
+            BuiltIn::AlsXZ => als::als_xz(sudoku),
+            BuiltIn::AlsXYWing => als::als_xy_wing(sudoku),
+            BuiltIn::DeathBlossom => als::death_blossom(sudoku),
+            BuiltIn::SueDeCoq => intersections::sue_de_coq(sudoku),
+            BuiltIn::CellForcingChain => forcing::cell_forcing_chain(sudoku),
+            BuiltIn::RegionForcingChain => forcing::region_forcing_chain(sudoku),
+            BuiltIn::Nishio => forcing::nishio(sudoku),
+            BuiltIn::JuniorExocet => exocet::junior_exocet(sudoku),
+        }
+    }
+}
+
+/// Groups in which the value is an option for exactly two cells: one of them gets the value
+pub(crate) fn conjugate_pairs(sudoku: &Sudoku, value: usize) -> Vec<(Group, usize, usize)> {
+    Group::all()
+        .filter_map(|group| {
+            let cells = GroupOptions::of(sudoku, group).cells_for_any(value.into());
+            if cells.count_ones() == 2 {
+                let mut pair = indices(cells);
+                Some((group, pair.next()?, pair.next()?))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Removes the value from the cells that see all of the given cells
+pub(crate) fn eliminations_seeing_all(
+    sudoku: &Sudoku,
+    cells: &[usize],
+    value: usize,
+) -> Vec<Candidate> {
+    sudoku
+        .open_cells()
+        .filter(|cell| {
+            cell.can_choose(value) && cells.iter().all(|other| cell.sees(sudoku.cell(*other)))
+        })
+        .map(|cell| Candidate::new(cell.index, value))
+        .collect()
+}
+
+/// All ways to pick size items, keeping their order
+pub(crate) fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        vec![Vec::new()]
+    } else if items.len() < size {
+        Vec::new()
+    } else {
+        let mut result: Vec<Vec<T>> = combinations(&items[1..], size - 1)
+            .into_iter()
+            .map(|mut rest| {
+                rest.insert(0, items[0]);
+                rest
+            })
+            .collect();
+        result.extend(combinations(&items[1..], size));
+        result
+    }
+}
+
+/// An empty puzzle in which the given cells only keep the given options
+#[cfg(test)]
+pub(crate) fn with_options(cells: &[(usize, &[usize])]) -> crate::SudokuResult<Sudoku> {
+    let mut sudoku = Sudoku::new();
+
+    for (cell_index, options) in cells {
+        for value in 1..=crate::GRID_COLUMNS {
+            if !options.contains(&value) {
+                sudoku.eliminate_option(*cell_index, value)?;
+            }
+        }
+    }
+
+    Ok(sudoku)
+}
+
+/// Removes the value from the cells of the group that aren't kept
+#[cfg(test)]
+pub(crate) fn keep_only(
+    sudoku: &mut Sudoku,
+    group: Group,
+    value: usize,
+    keep: &[usize],
+) -> crate::SudokuResult {
+    let cells: Vec<usize> = sudoku.cells_of(group).map(|cell| cell.index).collect();
+    for cell_index in cells {
+        if !keep.contains(&cell_index) {
+            sudoku.eliminate_option(cell_index, value)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn pick_combinations() {
+    assert_eq!(
+        combinations(&[1, 2, 3, 4], 3),
+        vec![vec![1, 2, 3], vec![1, 2, 4], vec![1, 3, 4], vec![2, 3, 4]]
+    );
+    assert_eq!(combinations(&[1, 2], 3), Vec::<Vec<usize>>::new());
+}
+
+#[cfg(test)]
+#[test]
+fn uniqueness_is_opt_in() {
+    assert!(BuiltIn::UNIQUENESS
+        .iter()
+        .all(|technique| !BuiltIn::DEFAULT.contains(technique)
+            && BuiltIn::DEFAULT_ASSUMING_UNIQUE.contains(technique)));
+    assert_eq!(
+        BuiltIn::DEFAULT_ASSUMING_UNIQUE.len(),
+        BuiltIn::DEFAULT.len() + BuiltIn::UNIQUENESS.len()
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn simplest_first() {
+    for techniques in [
+        BuiltIn::DEFAULT,
+        BuiltIn::UNIQUENESS,
+        BuiltIn::DEFAULT_ASSUMING_UNIQUE,
+        BuiltIn::LAST_RESORT,
+    ] {
+        assert!(techniques
+            .windows(2)
+            .all(|pair| pair[0].difficulty() <= pair[1].difficulty()));
+    }
+}
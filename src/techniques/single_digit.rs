@@ -0,0 +1,315 @@
+use crate::{
+    techniques::{conjugate_pairs, eliminations_seeing_all},
+    Candidate, Evidence, Group, GroupOptions, Link, Step, Sudoku, GRID_COLUMNS, GRID_SQUARE_SIDE,
+};
+
+#[cfg(test)]
+use crate::techniques::keep_only;
+
+/// Two strong links on the same value in parallel lines, with one end of each in a shared line:
+/// one of the other two ends has the value, so cells seeing both lose it
+pub(crate) fn skyscraper(sudoku: &Sudoku) -> Option<Step> {
+    (1..=GRID_COLUMNS).find_map(|value| {
+        let pairs = conjugate_pairs(sudoku, value);
+
+        [is_row, is_column].into_iter().find_map(|is_line| {
+            let lines: Vec<&(Group, usize, usize)> =
+                pairs.iter().filter(|(group, ..)| is_line(group)).collect();
+
+            lines.iter().enumerate().find_map(|(nr, first)| {
+                lines[nr + 1..].iter().find_map(|second| {
+                    ends(first).into_iter().find_map(|(start, first_joint)| {
+                        ends(second).into_iter().find_map(|(second_joint, end)| {
+                            let (joint, other_joint) =
+                                (sudoku.cell(first_joint), sudoku.cell(second_joint));
+                            let (start_cell, end_cell) = (sudoku.cell(start), sudoku.cell(end));
+                            let shares_line = if is_row(&first.0) {
+                                joint.column == other_joint.column
+                                    && start_cell.column != end_cell.column
+                            } else {
+                                joint.row == other_joint.row && start_cell.row != end_cell.row
+                            };
+
+                            if shares_line {
+                                chain_step(
+                                    sudoku,
+                                    "Skyscraper",
+                                    value,
+                                    [start, first_joint, second_joint, end],
+                                )
+                            } else {
+                                None
+                            }
+                        })
+                    })
+                })
+            })
+        })
+    })
+}
+
+/// A strong link in a row and one in a column, with one end of each in the same square: one of
+/// the other two ends has the value, so cells seeing both lose it
+pub(crate) fn two_string_kite(sudoku: &Sudoku) -> Option<Step> {
+    (1..=GRID_COLUMNS).find_map(|value| {
+        let pairs = conjugate_pairs(sudoku, value);
+        let rows = pairs.iter().filter(|(group, ..)| is_row(group));
+
+        rows.flat_map(|row| {
+            pairs
+                .iter()
+                .filter(|(group, ..)| is_column(group))
+                .map(move |column| (row, column))
+        })
+        .find_map(|(row, column)| {
+            ends(row).into_iter().find_map(|(start, row_joint)| {
+                ends(column).into_iter().find_map(|(column_joint, end)| {
+                    let cells = [start, row_joint, column_joint, end];
+                    let distinct = cells
+                        .iter()
+                        .enumerate()
+                        .all(|(nr, cell)| !cells[nr + 1..].contains(cell));
+
+                    if distinct
+                        && sudoku.cell(row_joint).square_nr == sudoku.cell(column_joint).square_nr
+                    {
+                        chain_step(sudoku, "Two-String Kite", value, cells)
+                    } else {
+                        None
+                    }
+                })
+            })
+        })
+    })
+}
+
+/// The options for a value in a square lie on one row and one column of it. A strong link that
+/// starts in that row (or column) outside the square means the value is either at its other end,
+/// or in the square's column (or row): the cell seeing both loses it.
+pub(crate) fn empty_rectangle(sudoku: &Sudoku) -> Option<Step> {
+    (1..=GRID_COLUMNS).find_map(|value| {
+        let pairs = conjugate_pairs(sudoku, value);
+
+        (0..GRID_COLUMNS).find_map(|square_nr| {
+            let cells: Vec<usize> = crate::indices(
+                GroupOptions::of(sudoku, Group::Square(square_nr)).cells_for_any(value.into()),
+            )
+            .collect();
+            if cells.len() < 2 {
+                return None;
+            }
+
+            let first_row = square_nr / GRID_SQUARE_SIDE * GRID_SQUARE_SIDE;
+            let first_column = square_nr % GRID_SQUARE_SIDE * GRID_SQUARE_SIDE;
+
+            (first_row..first_row + GRID_SQUARE_SIDE).find_map(|row_nr| {
+                (first_column..first_column + GRID_SQUARE_SIDE).find_map(|column_nr| {
+                    let on_cross = cells.iter().all(|cell_index| {
+                        let cell = sudoku.cell(*cell_index);
+                        cell.row == row_nr || cell.column == column_nr
+                    });
+                    let spread = cells.iter().any(|index| sudoku.cell(*index).row != row_nr)
+                        && cells
+                            .iter()
+                            .any(|index| sudoku.cell(*index).column != column_nr);
+
+                    if on_cross && spread {
+                        pairs.iter().find_map(|pair| {
+                            empty_rectangle_with(sudoku, value, square_nr, row_nr, column_nr, pair)
+                        })
+                    } else {
+                        None
+                    }
+                })
+            })
+        })
+    })
+}
+
+fn empty_rectangle_with(
+    sudoku: &Sudoku,
+    value: usize,
+    square_nr: usize,
+    row_nr: usize,
+    column_nr: usize,
+    (group, one, other): &(Group, usize, usize),
+) -> Option<Step> {
+    [(*one, *other), (*other, *one)]
+        .into_iter()
+        .find_map(|(near, far)| {
+            let (near_cell, far_cell) = (sudoku.cell(near), sudoku.cell(far));
+            if near_cell.square_nr == square_nr || far_cell.square_nr == square_nr {
+                return None;
+            }
+
+            let target = match group {
+                Group::Column(_) if near_cell.row == row_nr => {
+                    far_cell.row * GRID_COLUMNS + column_nr
+                }
+                Group::Row(_) if near_cell.column == column_nr => {
+                    row_nr * GRID_COLUMNS + far_cell.column
+                }
+                _ => return None,
+            };
+
+            let target_cell = sudoku.cell(target);
+            if target_cell.square_nr == square_nr || !target_cell.can_choose(value) {
+                return None;
+            }
+
+            Some(Step {
+                technique: "Empty Rectangle",
+                placements: Vec::new(),
+                eliminations: vec![Candidate::new(target, value)],
+                evidence: Evidence::EmptyRectangle {
+                    value,
+                    square: Group::Square(square_nr),
+                    row: Group::Row(row_nr),
+                    column: Group::Column(column_nr),
+                    link: Link::new(
+                        Candidate::new(near, value),
+                        Candidate::new(far, value),
+                        true,
+                    ),
+                },
+            })
+        })
+}
+
+/// A strong link, a weak link between the two joints, and another strong link
+fn chain_step(
+    sudoku: &Sudoku,
+    technique: &'static str,
+    value: usize,
+    [start, first_joint, second_joint, end]: [usize; 4],
+) -> Option<Step> {
+    if !sudoku.cell(first_joint).sees(sudoku.cell(second_joint)) {
+        return None;
+    }
+
+    let eliminations = eliminations_seeing_all(sudoku, &[start, end], value);
+    if eliminations.is_empty() {
+        return None;
+    }
+
+    let candidate = |cell_index| Candidate::new(cell_index, value);
+    Some(Step {
+        technique,
+        placements: Vec::new(),
+        eliminations,
+        evidence: Evidence::Chain {
+            links: vec![
+                Link::new(candidate(start), candidate(first_joint), true),
+                Link::new(candidate(first_joint), candidate(second_joint), false),
+                Link::new(candidate(second_joint), candidate(end), true),
+            ],
+        },
+    })
+}
+
+/// Both ways to walk a strong link
+fn ends((_, one, other): &(Group, usize, usize)) -> [(usize, usize); 2] {
+    [(*one, *other), (*other, *one)]
+}
+
+fn is_row(group: &Group) -> bool {
+    matches!(group, Group::Row(_))
+}
+
+fn is_column(group: &Group) -> bool {
+    matches!(group, Group::Column(_))
+}
+
+#[cfg(test)]
+#[test]
+fn skyscraper_on_rows() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+    keep_only(&mut sudoku, Group::Row(0), 5, &[1, 7])?;
+    keep_only(&mut sudoku, Group::Row(4), 5, &[37, 42])?;
+
+    let step = skyscraper(&sudoku).expect("a skyscraper");
+    assert_eq!(
+        step.evidence,
+        Evidence::Chain {
+            links: vec![
+                Link::new(Candidate::new(7, 5), Candidate::new(1, 5), true),
+                Link::new(Candidate::new(1, 5), Candidate::new(37, 5), false),
+                Link::new(Candidate::new(37, 5), Candidate::new(42, 5), true),
+            ]
+        }
+    );
+    assert_eq!(
+        step.eliminations,
+        [15, 24, 34, 52].map(|cell_index| Candidate::new(cell_index, 5))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn skyscraper_on_columns() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+    keep_only(&mut sudoku, Group::Column(0), 5, &[9, 63])?;
+    keep_only(&mut sudoku, Group::Column(4), 5, &[13, 58])?;
+
+    let step = skyscraper(&sudoku).expect("a skyscraper");
+    assert_eq!(
+        step.evidence,
+        Evidence::Chain {
+            links: vec![
+                Link::new(Candidate::new(63, 5), Candidate::new(9, 5), true),
+                Link::new(Candidate::new(9, 5), Candidate::new(13, 5), false),
+                Link::new(Candidate::new(13, 5), Candidate::new(58, 5), true),
+            ]
+        }
+    );
+    assert_eq!(
+        step.eliminations,
+        [55, 56, 66, 68].map(|cell_index| Candidate::new(cell_index, 5))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn two_string_kite_through_square() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+    keep_only(&mut sudoku, Group::Row(0), 4, &[0, 6])?;
+    keep_only(&mut sudoku, Group::Column(1), 4, &[19, 64])?;
+
+    let step = two_string_kite(&sudoku).expect("a two-string kite");
+    assert_eq!(
+        step.evidence,
+        Evidence::Chain {
+            links: vec![
+                Link::new(Candidate::new(6, 4), Candidate::new(0, 4), true),
+                Link::new(Candidate::new(0, 4), Candidate::new(19, 4), false),
+                Link::new(Candidate::new(19, 4), Candidate::new(64, 4), true),
+            ]
+        }
+    );
+    assert_eq!(step.eliminations, vec![Candidate::new(69, 4)]);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn empty_rectangle_with_column_link() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+    keep_only(&mut sudoku, Group::Square(0), 8, &[1, 9, 10, 11, 19])?;
+    keep_only(&mut sudoku, Group::Column(5), 8, &[14, 59])?;
+
+    let step = empty_rectangle(&sudoku).expect("an empty rectangle");
+    assert_eq!(
+        step.evidence,
+        Evidence::EmptyRectangle {
+            value: 8,
+            square: Group::Square(0),
+            row: Group::Row(1),
+            column: Group::Column(1),
+            link: Link::new(Candidate::new(14, 8), Candidate::new(59, 8), true),
+        }
+    );
+    assert_eq!(step.eliminations, vec![Candidate::new(55, 8)]);
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use crate::{Evidence, Step, Sudoku};
+
+/// A value that has only one place left in a row, column or square
+pub(crate) fn hidden_single(sudoku: &Sudoku) -> Option<Step> {
+    sudoku
+        .locate_single_options()
+        .map(|(group, placement)| Step {
+            technique: "Hidden single",
+            placements: vec![placement],
+            eliminations: Vec::new(),
+            evidence: Evidence::HiddenSingle { group },
+        })
+}
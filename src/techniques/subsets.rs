@@ -0,0 +1,193 @@
+use crate::{
+    indices, techniques::combinations, Candidate, Candidates, Evidence, Group, GroupOptions, Step,
+    Sudoku,
+};
+
+#[cfg(test)]
+use crate::GRID_COLUMNS;
+
+/// N cells in a group that together have only N options: those values can be removed from
+/// the other cells in the group
+pub(crate) fn naked_subset(sudoku: &Sudoku, size: usize) -> Option<Step> {
+    Group::all().find_map(|group| naked_subset_in(sudoku, group, size))
+}
+
+/// N values in a group that are confined to N cells: the other options can be removed from
+/// those cells
+pub(crate) fn hidden_subset(sudoku: &Sudoku, size: usize) -> Option<Step> {
+    Group::all().find_map(|group| hidden_subset_in(sudoku, group, size))
+}
+
+fn naked_subset_in(sudoku: &Sudoku, group: Group, size: usize) -> Option<Step> {
+    let open_cells: Vec<usize> = sudoku
+        .cells_of(group)
+        .filter(|cell| cell.has_options())
+        .map(|cell| cell.index)
+        .collect();
+
+    if open_cells.len() <= size {
+        return None;
+    }
+
+    combinations(&open_cells, size)
+        .into_iter()
+        .find_map(|cells| {
+            let values = cells.iter().fold(Candidates::NONE, |values, cell_index| {
+                values | sudoku.cell(*cell_index).options
+            });
+            if values.len() != size {
+                return None;
+            }
+
+            let eliminations = eliminations_outside(sudoku, &open_cells, &cells, values);
+            if eliminations.is_empty() {
+                None
+            } else {
+                Some(Step {
+                    technique: naked_name(size),
+                    placements: Vec::new(),
+                    eliminations,
+                    evidence: Evidence::Subset {
+                        group,
+                        cells,
+                        values,
+                    },
+                })
+            }
+        })
+}
+
+fn hidden_subset_in(sudoku: &Sudoku, group: Group, size: usize) -> Option<Step> {
+    let group_options = GroupOptions::of(sudoku, group);
+    let open_values: Vec<usize> = group_options.values().iter().collect();
+
+    if open_values.len() <= size {
+        return None;
+    }
+
+    combinations(&open_values, size)
+        .into_iter()
+        .find_map(|values| {
+            let values: Candidates = values.into_iter().collect();
+            let cells = group_options.cells_for_any(values);
+            if cells.count_ones() as usize != size {
+                return None;
+            }
+
+            let cells: Vec<usize> = indices(cells).collect();
+            let eliminations: Vec<Candidate> = cells
+                .iter()
+                .flat_map(|cell_index| {
+                    (sudoku.cell(*cell_index).options - values)
+                        .iter()
+                        .map(|value| Candidate::new(*cell_index, value))
+                })
+                .collect();
+
+            if eliminations.is_empty() {
+                None
+            } else {
+                Some(Step {
+                    technique: hidden_name(size),
+                    placements: Vec::new(),
+                    eliminations,
+                    evidence: Evidence::Subset {
+                        group,
+                        cells,
+                        values,
+                    },
+                })
+            }
+        })
+}
+
+/// The options among the values in the open cells that are not part of the subset
+pub(crate) fn eliminations_outside(
+    sudoku: &Sudoku,
+    open_cells: &[usize],
+    cells: &[usize],
+    values: Candidates,
+) -> Vec<Candidate> {
+    open_cells
+        .iter()
+        .filter(|cell_index| !cells.contains(cell_index))
+        .flat_map(|cell_index| {
+            (sudoku.cell(*cell_index).options & values)
+                .iter()
+                .map(|value| Candidate::new(*cell_index, value))
+        })
+        .collect()
+}
+
+fn naked_name(size: usize) -> &'static str {
+    match size {
+        2 => "Naked pair",
+        3 => "Naked triple",
+        _ => "Naked quad",
+    }
+}
+
+fn hidden_name(size: usize) -> &'static str {
+    match size {
+        2 => "Hidden pair",
+        3 => "Hidden triple",
+        _ => "Hidden quad",
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn naked_pair_in_row() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    // leave 4 and 5 as the only options for the first two cells of the top row
+    for (cell_index, value) in [(9, 1), (10, 2), (11, 3), (18, 6), (19, 7), (20, 8), (27, 9)] {
+        sudoku.choose(cell_index, value)?;
+    }
+    sudoku.choose(55, 9)?;
+
+    let step = naked_subset(&sudoku, 2).expect("a naked pair");
+    assert_eq!(step.technique, "Naked pair");
+    assert_eq!(
+        step.evidence,
+        Evidence::Subset {
+            group: Group::Row(0),
+            cells: vec![0, 1],
+            values: [4, 5].into_iter().collect()
+        }
+    );
+    assert!(step.eliminations.contains(&Candidate::new(2, 4)));
+    assert!(step.eliminations.contains(&Candidate::new(8, 5)));
+    assert!(step
+        .eliminations
+        .iter()
+        .all(|candidate| candidate.cell_index > 1 && candidate.cell_index < GRID_COLUMNS));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn hidden_pair_in_row() -> crate::SudokuResult {
+    let mut sudoku = Sudoku::new();
+
+    // 1 and 2 are kept out of the top row, except for its first two cells
+    for (cell_index, value) in [(14, 1), (23, 2), (24, 1), (15, 2)] {
+        sudoku.choose(cell_index, value)?;
+    }
+    for (cell_index, value) in [(65, 1), (74, 2)] {
+        sudoku.choose(cell_index, value)?;
+    }
+
+    let step = hidden_subset(&sudoku, 2).expect("a hidden pair");
+    assert_eq!(step.technique, "Hidden pair");
+    assert_eq!(
+        step.evidence,
+        Evidence::Subset {
+            group: Group::Row(0),
+            cells: vec![0, 1],
+            values: [1, 2].into_iter().collect()
+        }
+    );
+    assert_eq!(step.eliminations.len(), 14);
+    Ok(())
+}
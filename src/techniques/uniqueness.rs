@@ -0,0 +1,493 @@
+use crate::{
+    techniques::{combinations, eliminations_seeing_all, subsets::eliminations_outside},
+    Candidate, Candidates, CellIndices, Evidence, Group, GroupOptions, Step, Sudoku, GRID_COLUMNS,
+    GRID_ROWS, GRID_SQUARE_SIDE,
+};
+
+/// Four cells on two rows, two columns and two squares that all have the two values as option.
+/// Were those their only options, the values could swap places for a second solution, so in a
+/// puzzle with a unique solution at least one cell gets another value.
+struct Rectangle {
+    /// Top left, top right, bottom left, bottom right
+    cells: [usize; 4],
+    values: Candidates,
+}
+
+impl Rectangle {
+    /// The cells with only the two values as options
+    fn floor(&self, sudoku: &Sudoku) -> Vec<usize> {
+        self.cells
+            .into_iter()
+            .filter(|cell_index| sudoku.cell(*cell_index).options == self.values)
+            .collect()
+    }
+
+    /// The cells with more options than the two values
+    fn roof(&self, sudoku: &Sudoku) -> Vec<usize> {
+        self.cells
+            .into_iter()
+            .filter(|cell_index| sudoku.cell(*cell_index).options != self.values)
+            .collect()
+    }
+
+    fn extras(&self, sudoku: &Sudoku, cell_index: usize) -> Candidates {
+        sudoku.cell(cell_index).options - self.values
+    }
+
+    /// The one extra option all roof cells share, if that is all they have extra
+    fn single_extra(&self, sudoku: &Sudoku, roof: &[usize]) -> Option<usize> {
+        let extras = roof.iter().fold(Candidates::NONE, |extras, cell_index| {
+            extras | self.extras(sudoku, *cell_index)
+        });
+        extras.single_value().filter(|_| {
+            roof.iter()
+                .all(|cell_index| self.extras(sudoku, *cell_index).len() == 1)
+        })
+    }
+
+    fn step(
+        &self,
+        type_nr: usize,
+        eliminations: Vec<Candidate>,
+        subset: Vec<usize>,
+    ) -> Option<Step> {
+        if eliminations.is_empty() {
+            None
+        } else {
+            Some(Step {
+                technique: rectangle_name(type_nr),
+                placements: Vec::new(),
+                eliminations,
+                evidence: Evidence::UniqueRectangle {
+                    cells: self.cells.to_vec(),
+                    values: self.values,
+                    subset,
+                },
+            })
+        }
+    }
+
+    /// Three cells with only the two values: the fourth can't have either
+    fn type_1(&self, sudoku: &Sudoku) -> Option<Step> {
+        let roof = self.roof(sudoku);
+        if roof.len() != 1 {
+            return None;
+        }
+
+        let eliminations = self
+            .values
+            .iter()
+            .map(|value| Candidate::new(roof[0], value))
+            .collect();
+        self.step(1, eliminations, Vec::new())
+    }
+
+    /// Two cells on a line with the same single extra option: one of them gets it, so cells
+    /// seeing both lose it
+    fn type_2(&self, sudoku: &Sudoku) -> Option<Step> {
+        let roof = self.roof(sudoku);
+        if roof.len() != 2 || !share_line(sudoku, roof[0], roof[1]) {
+            return None;
+        }
+
+        let extra = self.single_extra(sudoku, &roof)?;
+        self.step(2, eliminations_seeing_all(sudoku, &roof, extra), Vec::new())
+    }
+
+    /// Two cells on a line with extra options: together they act as a single cell with the
+    /// extras, which can form a naked subset with other cells of a group they share
+    fn type_3(&self, sudoku: &Sudoku) -> Option<Step> {
+        let roof = self.roof(sudoku);
+        if roof.len() != 2 || !share_line(sudoku, roof[0], roof[1]) {
+            return None;
+        }
+
+        let extras = self.extras(sudoku, roof[0]) | self.extras(sudoku, roof[1]);
+        shared_groups(roof[0], roof[1])
+            .into_iter()
+            .find_map(|group| {
+                let open_cells: Vec<usize> = sudoku
+                    .cells_of(group)
+                    .filter(|cell| cell.has_options())
+                    .map(|cell| cell.index)
+                    .collect();
+                let others: Vec<usize> = open_cells
+                    .iter()
+                    .copied()
+                    .filter(|cell_index| !roof.contains(cell_index))
+                    .collect();
+
+                (1..others.len().min(4)).find_map(|size| {
+                    combinations(&others, size).into_iter().find_map(|subset| {
+                        let values = subset.iter().fold(extras, |values, cell_index| {
+                            values | sudoku.cell(*cell_index).options
+                        });
+                        if values.len() != size + 1 {
+                            return None;
+                        }
+
+                        let cells: Vec<usize> = roof.iter().chain(subset.iter()).copied().collect();
+                        let eliminations =
+                            eliminations_outside(sudoku, &open_cells, &cells, values);
+                        self.step(3, eliminations, subset)
+                    })
+                })
+            })
+    }
+
+    /// Two cells in a group where one of the values can go nowhere else: they can't have the
+    /// other value
+    fn type_4(&self, sudoku: &Sudoku) -> Option<Step> {
+        let roof = self.roof(sudoku);
+        if roof.len() != 2 {
+            return None;
+        }
+
+        let roof_mask = (1_u128 << roof[0]) | (1 << roof[1]);
+        shared_groups(roof[0], roof[1])
+            .into_iter()
+            .find_map(|group| {
+                let group_options = GroupOptions::of(sudoku, group);
+                self.values.iter().find_map(|value| {
+                    if group_options.cells_for_any(value.into()) != roof_mask {
+                        return None;
+                    }
+
+                    let other = (self.values - value.into()).single_value()?;
+                    let eliminations = roof
+                        .iter()
+                        .map(|cell_index| Candidate::new(*cell_index, other))
+                        .collect();
+                    self.step(4, eliminations, Vec::new())
+                })
+            })
+    }
+
+    /// Two diagonal cells, or three cells, with the same single extra option: one of them gets
+    /// it, so cells seeing all of them lose it
+    fn type_5(&self, sudoku: &Sudoku) -> Option<Step> {
+        let roof = self.roof(sudoku);
+        let diagonal = roof.len() == 2 && !share_line(sudoku, roof[0], roof[1]);
+        if !diagonal && roof.len() != 3 {
+            return None;
+        }
+
+        let extra = self.single_extra(sudoku, &roof)?;
+        self.step(5, eliminations_seeing_all(sudoku, &roof, extra), Vec::new())
+    }
+
+    /// Two diagonal cells with only the two values, where one value is locked in the rectangle
+    /// on both rows or both columns: the other two cells can't have that value, or all four
+    /// would end up with the two values
+    fn type_6(&self, sudoku: &Sudoku) -> Option<Step> {
+        let floor = self.floor(sudoku);
+        if floor.len() != 2 || share_line(sudoku, floor[0], floor[1]) {
+            return None;
+        }
+
+        let [top_left, top_right, bottom_left, _] = self.cells.map(|index| sudoku.cell(index));
+        let rows = [top_left.row, bottom_left.row].map(Group::Row);
+        let columns = [top_left.column, top_right.column].map(Group::Column);
+        let in_rectangle = |group: Group| {
+            CellIndices::shared()
+                .for_group(group)
+                .iter()
+                .filter(|cell_index| self.cells.contains(cell_index))
+                .fold(0_u128, |cells, cell_index| cells | (1 << cell_index))
+        };
+
+        self.values.iter().find_map(|value| {
+            let locked = |groups: [Group; 2]| {
+                groups.into_iter().all(|group| {
+                    GroupOptions::of(sudoku, group).cells_for_any(value.into())
+                        == in_rectangle(group)
+                })
+            };
+
+            if locked(rows) || locked(columns) {
+                let eliminations = self
+                    .roof(sudoku)
+                    .into_iter()
+                    .map(|cell_index| Candidate::new(cell_index, value))
+                    .collect();
+                self.step(6, eliminations, Vec::new())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Looks for a unique rectangle of the given type. Only sound for puzzles with a single solution.
+pub(crate) fn unique_rectangle(sudoku: &Sudoku, type_nr: usize) -> Option<Step> {
+    rectangles(sudoku)
+        .into_iter()
+        .find_map(|rectangle| match type_nr {
+            1 => rectangle.type_1(sudoku),
+            2 => rectangle.type_2(sudoku),
+            3 => rectangle.type_3(sudoku),
+            4 => rectangle.type_4(sudoku),
+            5 => rectangle.type_5(sudoku),
+            _ => rectangle.type_6(sudoku),
+        })
+}
+
+/// All open cells but one have two options. When each option would appear exactly twice in
+/// every group without one value of the remaining cell, the puzzle would have several solutions
+/// unless the cell gets that value. Only sound for puzzles with a single solution.
+pub(crate) fn bivalue_universal_grave(sudoku: &Sudoku) -> Option<Step> {
+    let mut exceptions = sudoku.open_cells().filter(|cell| cell.todo_count != 2);
+    let cell = exceptions.next()?;
+    if cell.todo_count != 3 || exceptions.next().is_some() {
+        return None;
+    }
+
+    let cell_groups = CellIndices::shared().groups_of(cell.index);
+    let value = cell.available_options().find(|value| {
+        Group::all().all(|group| {
+            let group_options = GroupOptions::of(sudoku, group);
+            (1..=GRID_COLUMNS).all(|other| {
+                let mut count = group_options.cells_for_any(other.into()).count_ones();
+                if other == *value && cell_groups.contains(&group) {
+                    count -= 1;
+                }
+                count == 0 || count == 2
+            })
+        })
+    })?;
+
+    Some(Step {
+        technique: "BUG+1",
+        placements: vec![Candidate::new(cell.index, value)],
+        eliminations: Vec::new(),
+        evidence: Evidence::BivalueUniversalGrave {
+            cell_index: cell.index,
+        },
+    })
+}
+
+fn rectangles(sudoku: &Sudoku) -> Vec<Rectangle> {
+    let mut rectangles = Vec::new();
+
+    for top in 0..GRID_ROWS {
+        for bottom in top + 1..GRID_ROWS {
+            for left in 0..GRID_COLUMNS {
+                for right in left + 1..GRID_COLUMNS {
+                    let same_band = top / GRID_SQUARE_SIDE == bottom / GRID_SQUARE_SIDE;
+                    let same_stack = left / GRID_SQUARE_SIDE == right / GRID_SQUARE_SIDE;
+                    if same_band == same_stack {
+                        continue;
+                    }
+
+                    let cells = [
+                        top * GRID_COLUMNS + left,
+                        top * GRID_COLUMNS + right,
+                        bottom * GRID_COLUMNS + left,
+                        bottom * GRID_COLUMNS + right,
+                    ];
+                    if !cells
+                        .iter()
+                        .all(|cell_index| sudoku.cell(*cell_index).has_options())
+                    {
+                        continue;
+                    }
+
+                    let common = cells.iter().fold(Candidates::ALL, |common, cell_index| {
+                        common & sudoku.cell(*cell_index).options
+                    });
+                    let common: Vec<usize> = common.iter().collect();
+                    for values in combinations(&common, 2) {
+                        rectangles.push(Rectangle {
+                            cells,
+                            values: values.into_iter().collect(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    rectangles
+}
+
+fn share_line(sudoku: &Sudoku, one: usize, other: usize) -> bool {
+    let (one, other) = (sudoku.cell(one), sudoku.cell(other));
+    one.row == other.row || one.column == other.column
+}
+
+fn shared_groups(one: usize, other: usize) -> Vec<Group> {
+    let cell_indices = CellIndices::shared();
+    cell_indices
+        .groups_of(one)
+        .iter()
+        .filter(|group| cell_indices.groups_of(other).contains(group))
+        .copied()
+        .collect()
+}
+
+fn rectangle_name(type_nr: usize) -> &'static str {
+    match type_nr {
+        1 => "Unique Rectangle type 1",
+        2 => "Unique Rectangle type 2",
+        3 => "Unique Rectangle type 3",
+        4 => "Unique Rectangle type 4",
+        5 => "Unique Rectangle type 5",
+        _ => "Unique Rectangle type 6",
+    }
+}
+
+#[cfg(test)]
+use crate::techniques::{keep_only, with_options};
+
+#[cfg(test)]
+#[test]
+fn unique_rectangle_type_1() -> crate::SudokuResult {
+    // r1c1, r1c4 and r2c1 have only 1 and 2, r2c4 has them too
+    let sudoku = with_options(&[(0, &[1, 2]), (3, &[1, 2]), (9, &[1, 2])])?;
+
+    let step = unique_rectangle(&sudoku, 1).expect("a unique rectangle");
+    assert_eq!(step.technique, "Unique Rectangle type 1");
+    assert_eq!(
+        step.evidence,
+        Evidence::UniqueRectangle {
+            cells: vec![0, 3, 9, 12],
+            values: [1, 2].into_iter().collect(),
+            subset: vec![],
+        }
+    );
+    assert_eq!(
+        step.eliminations,
+        vec![Candidate::new(12, 1), Candidate::new(12, 2)]
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn unique_rectangle_type_2() -> crate::SudokuResult {
+    let sudoku = with_options(&[
+        (0, &[1, 2]),
+        (9, &[1, 2]),
+        (3, &[1, 2, 7]),
+        (12, &[1, 2, 7]),
+    ])?;
+
+    let step = unique_rectangle(&sudoku, 2).expect("a unique rectangle");
+    // the rest of column 4 and of the top middle square
+    assert_eq!(step.eliminations.len(), 13);
+    assert!(step.eliminations.contains(&Candidate::new(4, 7)));
+    assert!(step.eliminations.contains(&Candidate::new(75, 7)));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn unique_rectangle_type_3() -> crate::SudokuResult {
+    let sudoku = with_options(&[
+        (0, &[1, 2]),
+        (9, &[1, 2]),
+        (3, &[1, 2, 5]),
+        (12, &[1, 2, 6]),
+        (30, &[5, 6]),
+    ])?;
+
+    let step = unique_rectangle(&sudoku, 3).expect("a unique rectangle");
+    assert_eq!(
+        step.evidence,
+        Evidence::UniqueRectangle {
+            cells: vec![0, 3, 9, 12],
+            values: [1, 2].into_iter().collect(),
+            subset: vec![30],
+        }
+    );
+    assert_eq!(step.eliminations.len(), 12);
+    assert!(step.eliminations.contains(&Candidate::new(21, 5)));
+    assert!(step.eliminations.contains(&Candidate::new(75, 6)));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn unique_rectangle_type_4() -> crate::SudokuResult {
+    let mut sudoku = with_options(&[(0, &[1, 2]), (9, &[1, 2])])?;
+    keep_only(&mut sudoku, Group::Column(3), 1, &[3, 12])?;
+
+    let step = unique_rectangle(&sudoku, 4).expect("a unique rectangle");
+    assert_eq!(
+        step.eliminations,
+        vec![Candidate::new(3, 2), Candidate::new(12, 2)]
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn unique_rectangle_type_5() -> crate::SudokuResult {
+    let sudoku = with_options(&[
+        (0, &[1, 2]),
+        (12, &[1, 2]),
+        (3, &[1, 2, 7]),
+        (9, &[1, 2, 7]),
+    ])?;
+
+    let step = unique_rectangle(&sudoku, 5).expect("a unique rectangle");
+    assert_eq!(
+        step.eliminations,
+        [1, 2, 13, 14].map(|cell_index| Candidate::new(cell_index, 7))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn unique_rectangle_type_6() -> crate::SudokuResult {
+    let mut sudoku = with_options(&[
+        (0, &[1, 2]),
+        (12, &[1, 2]),
+        (3, &[1, 2, 5]),
+        (9, &[1, 2, 6]),
+    ])?;
+    keep_only(&mut sudoku, Group::Row(0), 1, &[0, 3])?;
+    keep_only(&mut sudoku, Group::Row(1), 1, &[9, 12])?;
+
+    let step = unique_rectangle(&sudoku, 6).expect("a unique rectangle");
+    assert_eq!(
+        step.eliminations,
+        vec![Candidate::new(3, 1), Candidate::new(9, 1)]
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn bug_plus_one() -> crate::SudokuResult {
+    // every cell has its value in two shifted grids as options, r1c1 has 5 as well
+    let options: Vec<(usize, Vec<usize>)> = (0..crate::GRID_SIZE)
+        .map(|cell_index| {
+            let (row, column) = (cell_index / GRID_COLUMNS, cell_index % GRID_COLUMNS);
+            let value =
+                (row * GRID_SQUARE_SIDE + row / GRID_SQUARE_SIDE + column) % GRID_COLUMNS + 1;
+            let mut options = vec![value, value % GRID_COLUMNS + 1];
+            if cell_index == 0 {
+                options.push(5);
+            }
+            (cell_index, options)
+        })
+        .collect();
+    let options: Vec<(usize, &[usize])> = options
+        .iter()
+        .map(|(cell_index, options)| (*cell_index, options.as_slice()))
+        .collect();
+    let sudoku = with_options(&options)?;
+
+    assert_eq!(
+        bivalue_universal_grave(&sudoku),
+        Some(Step {
+            technique: "BUG+1",
+            placements: vec![Candidate::new(0, 5)],
+            eliminations: vec![],
+            evidence: Evidence::BivalueUniversalGrave { cell_index: 0 },
+        })
+    );
+    Ok(())
+}
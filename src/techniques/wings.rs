@@ -0,0 +1,228 @@
+use crate::{
+    techniques::{conjugate_pairs, eliminations_seeing_all},
+    Candidate, Candidates, Cell, Evidence, Link, Step, Sudoku,
+};
+
+#[cfg(test)]
+use crate::techniques::with_options;
+
+/// A pivot with options xy sees a pincer with xz and a pincer with yz: whichever value the pivot
+/// gets, one of the pincers becomes z, so cells seeing both pincers cannot be z
+pub(crate) fn xy_wing(sudoku: &Sudoku) -> Option<Step> {
+    let bivalue: Vec<&Cell> = sudoku
+        .open_cells()
+        .filter(|cell| cell.todo_count == 2)
+        .collect();
+
+    bivalue.iter().find_map(|pivot| {
+        let pincers: Vec<&&Cell> = bivalue
+            .iter()
+            .filter(|cell| pivot.sees(cell) && (cell.options & pivot.options).len() == 1)
+            .collect();
+
+        pincers.iter().enumerate().find_map(|(nr, first)| {
+            pincers[nr + 1..].iter().find_map(|second| {
+                let shared = first.options & second.options;
+                let value = shared.single_value()?;
+                if pivot.options != (first.options | second.options) - shared {
+                    return None;
+                }
+
+                wing_step(
+                    sudoku,
+                    "XY-Wing",
+                    pivot,
+                    &[first, second],
+                    value,
+                    &[first, second],
+                )
+            })
+        })
+    })
+}
+
+/// Like the XY-Wing, but the pivot has the options xyz as well, so the cells that lose z must
+/// also see the pivot
+pub(crate) fn xyz_wing(sudoku: &Sudoku) -> Option<Step> {
+    let bivalue: Vec<&Cell> = sudoku
+        .open_cells()
+        .filter(|cell| cell.todo_count == 2)
+        .collect();
+
+    sudoku
+        .open_cells()
+        .filter(|cell| cell.todo_count == 3)
+        .find_map(|pivot| {
+            let pincers: Vec<&&Cell> = bivalue
+                .iter()
+                .filter(|cell| pivot.sees(cell) && (cell.options - pivot.options).is_empty())
+                .collect();
+
+            pincers.iter().enumerate().find_map(|(nr, first)| {
+                pincers[nr + 1..].iter().find_map(|second| {
+                    let value = (first.options & second.options).single_value()?;
+                    if (first.options | second.options) != pivot.options {
+                        return None;
+                    }
+
+                    wing_step(
+                        sudoku,
+                        "XYZ-Wing",
+                        pivot,
+                        &[first, second],
+                        value,
+                        &[pivot, first, second],
+                    )
+                })
+            })
+        })
+}
+
+fn wing_step(
+    sudoku: &Sudoku,
+    technique: &'static str,
+    pivot: &Cell,
+    pincers: &[&Cell],
+    value: usize,
+    seen: &[&Cell],
+) -> Option<Step> {
+    let seen: Vec<usize> = seen.iter().map(|cell| cell.index).collect();
+    let eliminations = eliminations_seeing_all(sudoku, &seen, value);
+
+    if eliminations.is_empty() {
+        None
+    } else {
+        Some(Step {
+            technique,
+            placements: Vec::new(),
+            eliminations,
+            evidence: Evidence::Wing {
+                pivot: pivot.index,
+                pincers: pincers.iter().map(|cell| cell.index).collect(),
+                value,
+            },
+        })
+    }
+}
+
+/// Two cells with the same options xy that do not see each other, joined by a strong link on x:
+/// one of them is y, so cells seeing both cannot be y
+pub(crate) fn w_wing(sudoku: &Sudoku) -> Option<Step> {
+    let bivalue: Vec<&Cell> = sudoku
+        .open_cells()
+        .filter(|cell| cell.todo_count == 2)
+        .collect();
+
+    bivalue.iter().enumerate().find_map(|(nr, first)| {
+        bivalue[nr + 1..]
+            .iter()
+            .filter(|second| second.options == first.options && !first.sees(second))
+            .find_map(|second| {
+                first
+                    .options
+                    .iter()
+                    .find_map(|linked| w_wing_between(sudoku, first, second, linked))
+            })
+    })
+}
+
+fn w_wing_between(sudoku: &Sudoku, first: &Cell, second: &Cell, linked: usize) -> Option<Step> {
+    let value = (first.options - Candidates::single(linked)).first()?;
+    let pair = [first.index, second.index];
+
+    let link = conjugate_pairs(sudoku, linked)
+        .into_iter()
+        .flat_map(|(_, one, other)| [(one, other), (other, one)])
+        .find(|(near, far)| {
+            !pair.contains(near)
+                && !pair.contains(far)
+                && first.sees(sudoku.cell(*near))
+                && second.sees(sudoku.cell(*far))
+        })?;
+
+    let eliminations = eliminations_seeing_all(sudoku, &pair, value);
+    if eliminations.is_empty() {
+        None
+    } else {
+        Some(Step {
+            technique: "W-Wing",
+            placements: Vec::new(),
+            eliminations,
+            evidence: Evidence::WWing {
+                cells: pair.to_vec(),
+                link: Link::new(
+                    Candidate::new(link.0, linked),
+                    Candidate::new(link.1, linked),
+                    true,
+                ),
+                value,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn xy_wing_pincers() -> crate::SudokuResult {
+    // pivot r1c1 {1,2}, pincers r1c5 {1,3} and r5c1 {2,3}
+    let sudoku = with_options(&[(0, &[1, 2]), (4, &[1, 3]), (36, &[2, 3])])?;
+
+    let step = xy_wing(&sudoku).expect("an XY-Wing");
+    assert_eq!(
+        step.evidence,
+        Evidence::Wing {
+            pivot: 0,
+            pincers: vec![4, 36],
+            value: 3
+        }
+    );
+    assert_eq!(step.eliminations, vec![Candidate::new(40, 3)]);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn xyz_wing_pincers() -> crate::SudokuResult {
+    // pivot r1c1 {1,2,3}, pincers r1c5 {1,3} and r2c2 {2,3}
+    let sudoku = with_options(&[(0, &[1, 2, 3]), (4, &[1, 3]), (10, &[2, 3])])?;
+
+    let step = xyz_wing(&sudoku).expect("an XYZ-Wing");
+    assert_eq!(
+        step.evidence,
+        Evidence::Wing {
+            pivot: 0,
+            pincers: vec![4, 10],
+            value: 3
+        }
+    );
+    assert_eq!(
+        step.eliminations,
+        vec![Candidate::new(1, 3), Candidate::new(2, 3)]
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn w_wing_link() -> crate::SudokuResult {
+    // r1c1 and r5c5 are {1,2}, and in column 9 the 1 is either in row 1 or row 5
+    let mut sudoku = with_options(&[(0, &[1, 2]), (40, &[1, 2])])?;
+    for row_nr in [1, 2, 3, 5, 6, 7, 8] {
+        sudoku.eliminate_option(row_nr * crate::GRID_COLUMNS + 8, 1)?;
+    }
+
+    let step = w_wing(&sudoku).expect("a W-Wing");
+    assert_eq!(
+        step.evidence,
+        Evidence::WWing {
+            cells: vec![0, 40],
+            link: Link::new(Candidate::new(8, 1), Candidate::new(44, 1), true),
+            value: 2
+        }
+    );
+    assert_eq!(
+        step.eliminations,
+        vec![Candidate::new(4, 2), Candidate::new(36, 2)]
+    );
+    Ok(())
+}